@@ -19,13 +19,16 @@ async function main() {
     const operatorKeypair = anchor.web3.Keypair.fromSecretKey(operatorPrivateKey);
     const admin = new anchor.web3.PublicKey(Deployment.admin);
 
+    // create game | accept bet | post result | void
+    const permissions = 0b1111;
     const txId = await program
         .methods
-        .assignOperator()
+        .assignOperator(permissions)
         .accounts({
             owner: ownerKeypair.publicKey,
             operator: operatorKeypair.publicKey,
             admin: admin,
+            systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([ownerKeypair])
         .rpc({
@@ -55,12 +55,22 @@ async function main() {
         ],
         program.programId,
     );
+    const [position] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+            Buffer.from("position"),
+            game.toBuffer(),
+            userKeypair.publicKey.toBuffer(),
+        ],
+        program.programId,
+    );
+    const betIndex = (await program.account.position.fetchNullable(position))?.bets ?? 0;
     const [credential] = anchor.web3.PublicKey.findProgramAddressSync(
         [
             Buffer.from("credential"),
             club.toBuffer(),
             userKeypair.publicKey.toBuffer(),
             Buffer.from(game_identifier),
+            new BN(betIndex).toArrayLike(Buffer, "le", 4),
         ],
         program.programId,
     );
@@ -84,6 +94,7 @@ async function main() {
             club: club,
             clubAuthority: clubAuthority,
             game: game,
+            position: position,
             credential: credential,
             tokenMint: tokenMint,
             playerTokenAccount: userTokenAccount,
@@ -6,6 +6,7 @@ import { Buffer } from "buffer";
 import {ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress, TOKEN_PROGRAM_ID} from "@solana/spl-token";
 import { SportsProgram } from "../../target/types/sports_program";
 import { Deployment } from "../deployment";
+import BN from "bn.js";
 
 dotenv.config();
 
@@ -54,12 +55,22 @@ async function main() {
         ],
         program.programId,
     );
+    const [position] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+            Buffer.from("position"),
+            game.toBuffer(),
+            userKeypair.publicKey.toBuffer(),
+        ],
+        program.programId,
+    );
+    const betIndex = 0;
     const [credential] = anchor.web3.PublicKey.findProgramAddressSync(
         [
             Buffer.from("credential"),
             club.toBuffer(),
             userKeypair.publicKey.toBuffer(),
             Buffer.from(game_identifier),
+            new BN(betIndex).toArrayLike(Buffer, "le", 4),
         ],
         program.programId,
     );
@@ -76,10 +87,12 @@ async function main() {
         .accounts({
             player: userKeypair.publicKey,
             operator: operatorKeypair.publicKey,
+            payer: operatorKeypair.publicKey,
             admin: admin,
             club: club,
             clubAuthority: clubAuthority,
             game: game,
+            position: position,
             credential: credential,
             tokenMint: tokenMint,
             playerTokenAccount: userTokenAccount,
@@ -0,0 +1,65 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import * as bs58 from "bs58";
+import * as dotenv from "dotenv";
+import { Buffer } from "buffer";
+import { SportsProgram } from "../../target/types/sports_program";
+import { Deployment } from "../deployment";
+
+dotenv.config();
+
+async function main() {
+    // Configure the client to use the local cluster.
+    anchor.setProvider(anchor.AnchorProvider.env());
+
+    const program = anchor.workspace.SportsProgram as Program<SportsProgram>;
+
+    const ownerPrivateKey = bs58.decode(process.env.OWNER_PRIVATE_KEY || "");
+    const ownerKeypair = anchor.web3.Keypair.fromSecretKey(ownerPrivateKey);
+    const operatorPrivateKey = bs58.decode(process.env.OPERATOR_PRIVATE_KEY || "");
+    const operatorKeypair = anchor.web3.Keypair.fromSecretKey(operatorPrivateKey);
+    const admin = new anchor.web3.PublicKey(Deployment.admin);
+    const tokenMint = new anchor.web3.PublicKey(Deployment.tokenMint);
+    const club_identifier = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    const [club] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+            Buffer.from("club"),
+            admin.toBuffer(),
+            ownerKeypair.publicKey.toBuffer(),
+            tokenMint.toBuffer(),
+            Buffer.from(club_identifier),
+        ],
+        program.programId,
+    );
+    const game_identifier = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    const [game] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+            Buffer.from("game"),
+            club.toBuffer(),
+            Buffer.from(game_identifier),
+        ],
+        program.programId,
+    );
+
+    const direction = 0;
+    const txId = await program
+        .methods
+        .postResult(direction)
+        .accounts({
+            operator: operatorKeypair.publicKey,
+            admin: admin,
+            club: club,
+            game: game,
+        })
+        .signers([operatorKeypair])
+        .rpc({
+            skipPreflight: true,
+            commitment: "confirmed",
+            maxRetries: 5,
+        });
+    console.log("transaction id:", txId);
+}
+
+main().catch((err) => {
+    console.error(err);
+});
\ No newline at end of file
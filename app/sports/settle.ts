@@ -6,6 +6,7 @@ import { Buffer } from "buffer";
 import {ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress, TOKEN_PROGRAM_ID} from "@solana/spl-token";
 import { SportsProgram } from "../../target/types/sports_program";
 import { Deployment } from "../deployment";
+import BN from "bn.js";
 
 dotenv.config();
 
@@ -61,12 +62,22 @@ async function main() {
         ],
         program.programId,
     );
+    const [position] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+            Buffer.from("position"),
+            game.toBuffer(),
+            userKeypair.publicKey.toBuffer(),
+        ],
+        program.programId,
+    );
+    const betIndex = 0;
     const [credential] = anchor.web3.PublicKey.findProgramAddressSync(
         [
             Buffer.from("credential"),
             club.toBuffer(),
             userKeypair.publicKey.toBuffer(),
             Buffer.from(game_identifier),
+            new BN(betIndex).toArrayLike(Buffer, "le", 4),
         ],
         program.programId,
     );
@@ -77,18 +88,19 @@ async function main() {
         TOKEN_PROGRAM_ID,
     );
 
-    const direction = 0;
     const txId = await program
         .methods
-        .settle(direction)
+        .settle()
         .accounts({
+            cranker: operatorKeypair.publicKey,
             player: userKeypair.publicKey,
-            operator: operatorKeypair.publicKey,
+            payer: operatorKeypair.publicKey,
             feeReceiver: feeReceiver,
             admin: admin,
             club: club,
             clubAuthority: clubAuthority,
             game: game,
+            position: position,
             credential: credential,
             tokenMint: tokenMint,
             playerTokenAccount: userTokenAccount,
@@ -98,7 +110,7 @@ async function main() {
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .signers([operatorKeypair, userKeypair])
+        .signers([operatorKeypair])
         .rpc({
             skipPreflight: true,
             commitment: "confirmed",
@@ -5,6 +5,7 @@ import * as dotenv from "dotenv";
 import { Buffer } from "buffer";
 import { SportsProgram } from "../../target/types/sports_program";
 import { Deployment } from "../deployment";
+import BN from "bn.js";
 
 dotenv.config();
 
@@ -41,9 +42,17 @@ async function main() {
         program.programId,
     );
 
+    const market = {
+        fixture: game_identifier,
+        marketType: { moneyline: {} },
+        line: 0,
+    };
+    const outcomes = 3;
+    const startTime = new BN(Math.floor(Date.now() / 1000));
+    const cutoffTime = startTime.addn(2 * 60 * 60);
     const txId = await program
         .methods
-        .startGame(game_identifier)
+        .startGame(game_identifier, market, null, outcomes, startTime, cutoffTime)
         .accounts({
             operator: operatorKeypair.publicKey,
             admin: admin,
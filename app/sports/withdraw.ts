@@ -54,6 +54,7 @@ async function main() {
         .withdraw(amount)
         .accounts({
             owner: ownerKeypair.publicKey,
+            admin: admin,
             club: club,
             clubAuthority: clubAuthority,
             tokenMint: tokenMint,
@@ -3,8 +3,29 @@ use anchor_lang::prelude::*;
 #[error_code]
 #[derive(Eq, PartialEq)]
 pub enum SportsError {
+    // unused since operator permissions, kept so later error codes stay stable
     #[msg("Invalid operator")]
     InvalidOperator,
+    #[msg("Too many operators")]
+    TooManyOperators,
+    #[msg("Invalid permissions")]
+    InvalidPermissions,
+    #[msg("Operator lacks create game permission")]
+    MissingCreateGamePermission,
+    #[msg("Operator lacks accept bet permission")]
+    MissingAcceptBetPermission,
+    #[msg("Operator lacks post result permission")]
+    MissingPostResultPermission,
+    #[msg("Operator lacks void permission")]
+    MissingVoidPermission,
+    #[msg("Invalid fee")]
+    InvalidFee,
+    #[msg("Invalid pending owner")]
+    InvalidPendingOwner,
+    #[msg("Invalid guardian")]
+    InvalidGuardian,
+    #[msg("Paused")]
+    Paused,
 
     // Club
     #[msg("Cannot close club")]
@@ -19,14 +40,96 @@ pub enum SportsError {
     InvalidLockAmount,
     #[msg("Insufficient liquidity")]
     InsufficientLiquidity,
+    #[msg("Invalid bet limits")]
+    InvalidBetLimits,
+    #[msg("Stake below club minimum")]
+    StakeTooLow,
+    #[msg("Stake above club maximum")]
+    StakeTooHigh,
+    #[msg("Odds below club minimum")]
+    OddsTooLow,
+    #[msg("Odds above club maximum")]
+    OddsTooHigh,
+    #[msg("Game liability cap exceeded")]
+    GameLiabilityExceeded,
+    #[msg("Outcome liability cap exceeded")]
+    OutcomeLiabilityExceeded,
+    #[msg("Player liability cap exceeded")]
+    PlayerLiabilityExceeded,
     
     // Game
     #[msg("Invalid Identifier")]
     InvalidIdentifier,
+    #[msg("Invalid market")]
+    InvalidMarket,
+    #[msg("Invalid game mode")]
+    InvalidGameMode,
+    #[msg("Invalid metadata")]
+    InvalidMetadata,
+    #[msg("Invalid outcome count")]
+    InvalidOutcomes,
+    #[msg("Invalid game time")]
+    InvalidGameTime,
+    #[msg("Invalid game status")]
+    InvalidGameStatus,
+    #[msg("Game is not open for betting")]
+    GameNotOpen,
+    #[msg("Game is not cancelled")]
+    GameNotCancelled,
     #[msg("Game can not close")]
     GameCannotClose,
+    #[msg("Game result already posted")]
+    GameFinalized,
+    #[msg("Game result not posted")]
+    GameNotFinalized,
+    #[msg("Invalid settlements")]
+    InvalidSettlements,
     
     // Credential
     #[msg("Invalid direction")]
     InvalidDirection,
+    #[msg("Invalid remaining accounts")]
+    InvalidRemainingAccounts,
+    #[msg("Invalid player token account")]
+    InvalidPlayerTokenAccount,
+    #[msg("Position has open bets")]
+    PositionHasOpenBets,
+
+    // Intent
+    #[msg("Invalid signature")]
+    InvalidSignature,
+    #[msg("Bet intent expired")]
+    IntentExpired,
+    #[msg("Invalid intent nonce")]
+    InvalidNonce,
+    #[msg("Lock below minimum")]
+    LockBelowMinimum,
+
+    // Quote
+    #[msg("Pricing key not set")]
+    PricingKeyNotSet,
+    #[msg("Quote expired")]
+    QuoteExpired,
+    #[msg("Quote not expired")]
+    QuoteNotExpired,
+    #[msg("Stake exceeds quote")]
+    StakeExceedsQuote,
+
+    // Cash out
+    #[msg("Invalid cash out amount")]
+    InvalidCashOutAmount,
+
+    // Exchange
+    #[msg("Invalid offer amount")]
+    InvalidOfferAmount,
+
+    // Parlay
+    #[msg("Invalid parlay legs")]
+    InvalidParlayLegs,
+    #[msg("Duplicate parlay leg")]
+    DuplicateParlayLeg,
+    #[msg("Parlay odds above club maximum")]
+    ParlayOddsTooHigh,
+    #[msg("Parlay not resolved")]
+    ParlayNotResolved,
 }
\ No newline at end of file
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::state::Admin;
+use crate::{
+    state::{Admin, Club},
+    error::SportsError,
+};
 
 #[derive(Accounts)]
 pub struct InitAdmin<'info> {
@@ -8,7 +11,7 @@ pub struct InitAdmin<'info> {
     pub owner: Signer<'info>,
     pub fee_receiver: SystemAccount<'info>,
     // program accounts
-    #[account(init, payer = owner, space = 8 + Admin::SIZE)]
+    #[account(init, payer = owner, space = 8 + Admin::size(0))]
     pub admin: Account<'info, Admin>,
     // system program
     pub system_program: Program<'info, System>,
@@ -67,27 +70,119 @@ pub(crate) fn _change_fee_receiver(ctx: Context<ChangeFeeReceiver>) -> Result<()
     Ok(())
 }
 
+#[derive(Accounts)]
+pub struct ProposeOwner<'info> {
+    pub owner: Signer<'info>,
+    pub new_owner: SystemAccount<'info>,
+    // program accounts
+    #[account(mut, has_one = owner)]
+    pub admin: Account<'info, Admin>,
+}
+
+#[event]
+pub struct ProposeOwnerEvent {
+    pub admin: Pubkey,
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+}
+
+pub(crate) fn _propose_owner(ctx: Context<ProposeOwner>) -> Result<()> {
+    ctx.accounts.admin.propose_owner(ctx.accounts.new_owner.key());
+
+    emit!(ProposeOwnerEvent {
+        admin: ctx.accounts.admin.key(),
+        owner: ctx.accounts.owner.key(),
+        pending_owner: ctx.accounts.new_owner.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptOwner<'info> {
+    pub new_owner: Signer<'info>,
+    // program accounts
+    #[account(mut)]
+    pub admin: Account<'info, Admin>,
+}
+
+#[event]
+pub struct AcceptOwnerEvent {
+    pub admin: Pubkey,
+    pub old_owner: Pubkey,
+    pub new_owner: Pubkey,
+}
+
+pub(crate) fn _accept_owner(ctx: Context<AcceptOwner>) -> Result<()> {
+    let old_owner = ctx.accounts.admin.owner;
+    ctx.accounts.admin.accept_owner(ctx.accounts.new_owner.key())?;
+
+    emit!(AcceptOwnerEvent {
+        admin: ctx.accounts.admin.key(),
+        old_owner,
+        new_owner: ctx.accounts.new_owner.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelOwnerProposal<'info> {
+    pub owner: Signer<'info>,
+    // program accounts
+    #[account(mut, has_one = owner)]
+    pub admin: Account<'info, Admin>,
+}
+
+#[event]
+pub struct CancelOwnerProposalEvent {
+    pub admin: Pubkey,
+    pub pending_owner: Pubkey,
+}
+
+pub(crate) fn _cancel_owner_proposal(ctx: Context<CancelOwnerProposal>) -> Result<()> {
+    let pending_owner = ctx.accounts.admin.cancel_owner_proposal()?;
+
+    emit!(CancelOwnerProposalEvent {
+        admin: ctx.accounts.admin.key(),
+        pending_owner,
+    });
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct AssignOperator<'info> {
+    #[account(mut)]
     pub owner: Signer<'info>,
     pub operator: SystemAccount<'info>,
     // program accounts
-    #[account(mut, has_one = owner)]
+    #[account(
+        mut,
+        has_one = owner,
+        realloc = 8 + admin.size_with_operator(operator.key),
+        realloc::payer = owner,
+        realloc::zero = false,
+    )]
     pub admin: Account<'info, Admin>,
+    // system program
+    pub system_program: Program<'info, System>,
 }
 
 #[event]
 pub struct AssignOperatorEvent {
     pub admin: Pubkey,
     pub operator: Pubkey,
+    pub permissions: u8,
 }
 
-pub(crate) fn _assign_operator(ctx: Context<AssignOperator>) -> Result<()> {
-    ctx.accounts.admin.assign_operator(ctx.accounts.operator.key());
+pub(crate) fn _assign_operator(ctx: Context<AssignOperator>, permissions: u8) -> Result<()> {
+    ctx.accounts.admin.assign_operator(ctx.accounts.operator.key(), permissions)?;
 
     emit!(AssignOperatorEvent {
         admin: ctx.accounts.admin.key(),
         operator: ctx.accounts.operator.key(),
+        permissions,
     });
 
     Ok(())
@@ -95,11 +190,20 @@ pub(crate) fn _assign_operator(ctx: Context<AssignOperator>) -> Result<()> {
 
 #[derive(Accounts)]
 pub struct RemoveOperator<'info> {
+    #[account(mut)]
     pub owner: Signer<'info>,
     pub operator: SystemAccount<'info>,
     // program accounts
-    #[account(mut, has_one = owner)]
+    #[account(
+        mut,
+        has_one = owner,
+        realloc = 8 + admin.size_without_operator(operator.key),
+        realloc::payer = owner,
+        realloc::zero = false,
+    )]
     pub admin: Account<'info, Admin>,
+    // system program
+    pub system_program: Program<'info, System>,
 }
 
 #[event]
@@ -118,3 +222,116 @@ pub(crate) fn _remove_operator(ctx: Context<RemoveOperator>) -> Result<()> {
 
     Ok(())
 }
+
+#[derive(Accounts)]
+pub struct SetFees<'info> {
+    pub owner: Signer<'info>,
+    // program accounts
+    #[account(mut, has_one = owner)]
+    pub admin: Account<'info, Admin>,
+}
+
+#[event]
+pub struct SetFeesEvent {
+    pub admin: Pubkey,
+    pub club_creation_fee: u64,
+    pub settle_fee_point: u16,
+}
+
+pub(crate) fn _set_fees(
+    ctx: Context<SetFees>,
+    club_creation_fee: u64,
+    settle_fee_point: u16,
+) -> Result<()> {
+    ctx.accounts.admin.set_fees(club_creation_fee, settle_fee_point)?;
+
+    emit!(SetFeesEvent {
+        admin: ctx.accounts.admin.key(),
+        club_creation_fee,
+        settle_fee_point,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetClubFee<'info> {
+    pub owner: Signer<'info>,
+    // program accounts
+    #[account(has_one = owner)]
+    pub admin: Account<'info, Admin>,
+    #[account(mut, has_one = admin)]
+    pub club: Account<'info, Club>,
+}
+
+#[event]
+pub struct SetClubFeeEvent {
+    pub admin: Pubkey,
+    pub club: Pubkey,
+    pub settle_fee_point: Option<u16>,
+}
+
+pub(crate) fn _set_club_fee(ctx: Context<SetClubFee>, settle_fee_point: Option<u16>) -> Result<()> {
+    ctx.accounts.club.set_settle_fee_point(settle_fee_point)?;
+
+    emit!(SetClubFeeEvent {
+        admin: ctx.accounts.admin.key(),
+        club: ctx.accounts.club.key(),
+        settle_fee_point,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    pub owner: Signer<'info>,
+    // program accounts
+    #[account(mut, has_one = owner)]
+    pub admin: Account<'info, Admin>,
+}
+
+#[event]
+pub struct SetGuardianEvent {
+    pub admin: Pubkey,
+    pub guardian: Option<Pubkey>,
+}
+
+pub(crate) fn _set_guardian(ctx: Context<SetGuardian>, guardian: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.admin.guardian = guardian;
+
+    emit!(SetGuardianEvent {
+        admin: ctx.accounts.admin.key(),
+        guardian,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(constraint = admin.can_pause(authority.key) @ SportsError::InvalidGuardian)]
+    pub authority: Signer<'info>,
+    // program accounts
+    #[account(mut)]
+    pub admin: Account<'info, Admin>,
+}
+
+#[event]
+pub struct SetPausedEvent {
+    pub admin: Pubkey,
+    pub authority: Pubkey,
+    pub paused: bool,
+}
+
+pub(crate) fn _set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    ctx.accounts.admin.paused = paused;
+
+    emit!(SetPausedEvent {
+        admin: ctx.accounts.admin.key(),
+        authority: ctx.accounts.authority.key(),
+        paused,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,475 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked},
+};
+
+use crate::{
+    state::{Admin, Club, Game, MatchedBet, Offer, Side},
+    error::SportsError,
+};
+use super::game::split_prize;
+
+#[derive(Accounts)]
+#[instruction(identifier: [u8; 32])]
+pub struct PostOffer<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    // program accounts
+    pub admin: Box<Account<'info, Admin>>,
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = token_mint,
+        constraint = !club.is_paused(&admin) @ SportsError::Paused,
+    )]
+    pub club: Box<Account<'info, Club>>,
+    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
+    pub club_authority: SystemAccount<'info>,
+    #[account(mut, has_one = club)]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + Offer::SIZE,
+        seeds = [
+            b"offer",
+            game.key().as_ref(),
+            maker.key().as_ref(),
+            &identifier,
+        ],
+        bump,
+    )]
+    pub offer: Box<Account<'info, Offer>>,
+    // token accounts
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub maker_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = club_authority,
+    )]
+    pub supply_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    // system program
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct PostOfferEvent {
+    pub club: Pubkey,
+    pub game: Pubkey,
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub side: Side,
+    pub direction: u8,
+    pub odds: u64,
+    pub stake: u64,
+}
+
+pub(crate) fn _post_offer(
+    ctx: Context<PostOffer>,
+    identifier: [u8; 32],
+    side: Side,
+    direction: u8,
+    odds: u64,
+    stake: u64,
+) -> Result<()> {
+    require_gte!(odds, ctx.accounts.club.min_odds, SportsError::OddsTooLow);
+    require_gte!(ctx.accounts.club.max_odds, odds, SportsError::OddsTooHigh);
+    let escrow = Offer::escrow_for(side, stake, odds)?;
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.maker_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.supply_token_account.to_account_info(),
+            authority: ctx.accounts.maker.to_account_info(),
+        },
+    );
+    transfer_checked(cpi_ctx, escrow, ctx.accounts.token_mint.decimals)?;
+
+    // update club and game
+    ctx.accounts.game.open_exchange_bet(direction, Clock::get()?.unix_timestamp)?;
+    ctx.accounts.club.escrow(escrow);
+    // initialize offer
+    ctx.accounts.offer.club = ctx.accounts.club.key();
+    ctx.accounts.offer.game = ctx.accounts.game.key();
+    ctx.accounts.offer.maker = ctx.accounts.maker.key();
+    ctx.accounts.offer.identifier = identifier;
+    ctx.accounts.offer.side = side;
+    ctx.accounts.offer.direction = direction;
+    ctx.accounts.offer.odds = odds;
+    ctx.accounts.offer.remaining = stake;
+    ctx.accounts.offer.escrow = escrow;
+
+    emit!(PostOfferEvent {
+        club: ctx.accounts.club.key(),
+        game: ctx.accounts.game.key(),
+        offer: ctx.accounts.offer.key(),
+        maker: ctx.accounts.maker.key(),
+        side,
+        direction,
+        odds,
+        stake,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MatchOffer<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    // program accounts
+    pub admin: Box<Account<'info, Admin>>,
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = token_mint,
+        constraint = !club.is_paused(&admin) @ SportsError::Paused,
+    )]
+    pub club: Box<Account<'info, Club>>,
+    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
+    pub club_authority: SystemAccount<'info>,
+    #[account(mut, has_one = club)]
+    pub game: Box<Account<'info, Game>>,
+    #[account(mut, has_one = club, has_one = game, has_one = maker)]
+    pub offer: Box<Account<'info, Offer>>,
+    #[account(
+        init,
+        payer = taker,
+        space = 8 + MatchedBet::SIZE,
+        seeds = [
+            b"matched",
+            game.key().as_ref(),
+            &game.matched_bets.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub matched_bet: Box<Account<'info, MatchedBet>>,
+    // token accounts
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub taker_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = club_authority,
+    )]
+    pub supply_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    // system program
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct MatchOfferEvent {
+    pub club: Pubkey,
+    pub game: Pubkey,
+    pub offer: Pubkey,
+    pub matched_bet: Pubkey,
+    pub backer: Pubkey,
+    pub layer: Pubkey,
+    pub direction: u8,
+    pub odds: u64,
+    pub stake: u64,
+    pub liability: u64,
+}
+
+/// Takes the other side of `stake` of backer stake on an offer.
+pub(crate) fn _match_offer(ctx: Context<MatchOffer>, stake: u64) -> Result<()> {
+    let (stake, liability) = ctx.accounts.offer.fill(stake)?;
+    let escrow = ctx.accounts.offer.taker_escrow(stake, liability);
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.taker_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.supply_token_account.to_account_info(),
+            authority: ctx.accounts.taker.to_account_info(),
+        },
+    );
+    transfer_checked(cpi_ctx, escrow, ctx.accounts.token_mint.decimals)?;
+
+    // update club and game
+    let direction = ctx.accounts.offer.direction;
+    ctx.accounts.game.open_matched_bet(direction, Clock::get()?.unix_timestamp)?;
+    ctx.accounts.club.escrow(escrow);
+    // initialize matched bet
+    let (backer, layer) = match ctx.accounts.offer.side {
+        Side::Back => (ctx.accounts.offer.maker, ctx.accounts.taker.key()),
+        Side::Lay => (ctx.accounts.taker.key(), ctx.accounts.offer.maker),
+    };
+    ctx.accounts.matched_bet.club = ctx.accounts.club.key();
+    ctx.accounts.matched_bet.game = ctx.accounts.game.key();
+    ctx.accounts.matched_bet.offer = ctx.accounts.offer.key();
+    ctx.accounts.matched_bet.backer = backer;
+    ctx.accounts.matched_bet.layer = layer;
+    ctx.accounts.matched_bet.payer = ctx.accounts.taker.key();
+    ctx.accounts.matched_bet.direction = direction;
+    ctx.accounts.matched_bet.stake = stake;
+    ctx.accounts.matched_bet.liability = liability;
+
+    emit!(MatchOfferEvent {
+        club: ctx.accounts.club.key(),
+        game: ctx.accounts.game.key(),
+        offer: ctx.accounts.offer.key(),
+        matched_bet: ctx.accounts.matched_bet.key(),
+        backer,
+        layer,
+        direction,
+        odds: ctx.accounts.offer.odds,
+        stake,
+        liability,
+    });
+
+    // a fully matched offer has nothing left in escrow
+    if ctx.accounts.offer.remaining == 0 {
+        ctx.accounts.game.close_exchange_bet();
+        ctx.accounts.offer.close(ctx.accounts.maker.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    // anyone may clean up offers once the game is resolved
+    #[account(
+        constraint = authority.key() == maker.key()
+            || game.settlement_of(offer.direction).is_some()
+            @ SportsError::InvalidGameStatus,
+    )]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    // program accounts
+    pub admin: Box<Account<'info, Admin>>,
+    #[account(mut, has_one = admin, has_one = token_mint)]
+    pub club: Box<Account<'info, Club>>,
+    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
+    pub club_authority: SystemAccount<'info>,
+    #[account(mut, has_one = club)]
+    pub game: Box<Account<'info, Game>>,
+    #[account(mut, close = maker, has_one = club, has_one = game, has_one = maker)]
+    pub offer: Box<Account<'info, Offer>>,
+    // token accounts
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = maker,
+    )]
+    pub maker_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = club_authority,
+    )]
+    pub supply_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct CancelOfferEvent {
+    pub club: Pubkey,
+    pub game: Pubkey,
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub refund: u64,
+}
+
+/// Withdraws the unmatched part of an offer, matched bets stay open.
+pub(crate) fn _cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+    let refund = ctx.accounts.offer.escrow;
+
+    // update club and game
+    ctx.accounts.game.close_exchange_bet();
+    ctx.accounts.club.release_escrow(refund);
+
+    if refund > 0 {
+        let club = ctx.accounts.club.key();
+        let bumps = [ctx.bumps.club_authority];
+        let signer_seeds = &[
+            &[
+                b"authority".as_slice(),
+                club.as_ref(),
+                &bumps,
+            ][..],
+        ];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.supply_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.maker_token_account.to_account_info(),
+                authority: ctx.accounts.club_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, refund, ctx.accounts.token_mint.decimals)?;
+    }
+
+    emit!(CancelOfferEvent {
+        club: ctx.accounts.club.key(),
+        game: ctx.accounts.game.key(),
+        offer: ctx.accounts.offer.key(),
+        maker: ctx.accounts.maker.key(),
+        refund,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SettleMatched<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub backer: SystemAccount<'info>,
+    pub layer: SystemAccount<'info>,
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+    pub fee_receiver: SystemAccount<'info>,
+    // program accounts
+    #[account(has_one = fee_receiver)]
+    pub admin: Box<Account<'info, Admin>>,
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = token_mint,
+        constraint = !club.is_settle_paused(&admin) @ SportsError::Paused,
+    )]
+    pub club: Box<Account<'info, Club>>,
+    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
+    pub club_authority: SystemAccount<'info>,
+    #[account(mut, has_one = club)]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        mut,
+        close = payer,
+        has_one = club,
+        has_one = game,
+        has_one = backer,
+        has_one = layer,
+        has_one = payer,
+    )]
+    pub matched_bet: Box<Account<'info, MatchedBet>>,
+    // token accounts
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = token_mint,
+        associated_token::authority = backer,
+    )]
+    pub backer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = token_mint,
+        associated_token::authority = layer,
+    )]
+    pub layer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = club_authority,
+    )]
+    pub supply_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = token_mint,
+        associated_token::authority = fee_receiver,
+    )]
+    pub fee_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    // system program
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct SettleMatchedEvent {
+    pub club: Pubkey,
+    pub game: Pubkey,
+    pub matched_bet: Pubkey,
+    pub backer_payout: u64,
+    pub layer_payout: u64,
+    pub fee: u64,
+}
+
+/// Pays out a matched bet from the result recorded on its game, charging
+/// the settle fee on each side's net winnings.
+pub(crate) fn _settle_matched(ctx: Context<SettleMatched>) -> Result<()> {
+    let settlement = ctx.accounts.game
+        .settlement_of(ctx.accounts.matched_bet.direction)
+        .ok_or(SportsError::GameNotFinalized)?;
+    let (backer_amount, layer_amount) = ctx.accounts.matched_bet.payout(settlement);
+
+    // update club and game
+    ctx.accounts.game.close_exchange_bet();
+    ctx.accounts.club.release_escrow(backer_amount + layer_amount);
+
+    let fee_point = ctx.accounts.admin.settle_fee_point(&ctx.accounts.club);
+    let (_, backer_fee) = split_prize(
+        backer_amount.saturating_sub(ctx.accounts.matched_bet.stake),
+        fee_point,
+    );
+    let (_, layer_fee) = split_prize(
+        layer_amount.saturating_sub(ctx.accounts.matched_bet.liability),
+        fee_point,
+    );
+    let fee = backer_fee + layer_fee;
+
+    let club = ctx.accounts.club.key();
+    let bumps = [ctx.bumps.club_authority];
+    let signer_seeds = &[
+        &[
+            b"authority".as_slice(),
+            club.as_ref(),
+            &bumps,
+        ][..],
+    ];
+    let payouts = [
+        (ctx.accounts.backer_token_account.to_account_info(), backer_amount - backer_fee),
+        (ctx.accounts.layer_token_account.to_account_info(), layer_amount - layer_fee),
+        (ctx.accounts.fee_token_account.to_account_info(), fee),
+    ];
+    for (to, amount) in payouts {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.supply_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to,
+                authority: ctx.accounts.club_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
+    }
+
+    emit!(SettleMatchedEvent {
+        club,
+        game: ctx.accounts.game.key(),
+        matched_bet: ctx.accounts.matched_bet.key(),
+        backer_payout: backer_amount - backer_fee,
+        layer_payout: layer_amount - layer_fee,
+        fee,
+    });
+
+    Ok(())
+}
@@ -1,4 +1,8 @@
-use anchor_lang::{prelude::*, system_program::{Transfer as Send, transfer as send}};
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar,
+    system_program::{Transfer as Send, transfer as send},
+};
 use anchor_spl::{
     associated_token::AssociatedToken,
     token_interface::{
@@ -8,13 +12,18 @@ use anchor_spl::{
 };
 
 use crate::{
-    state::{Admin, Club, Game, Credential},
+    state::{
+        Admin, Club, Game, GameMetadata, GameMode, Credential, IntentNonce, Market, MarketType,
+        Permission, Position, QuoteFill, Settlement,
+        DEFAULT_MIN_ODDS, DEFAULT_MAX_ODDS, ODDS_DIVISOR,
+    },
     error::SportsError,
+    utils::verify_ed25519_signature,
 };
 
-const CLUB_CREATION_FEE: u64 = 5_000_000;
-const SETTLE_FEE_POINT: u128 = 50;
 const BASIS_DIVISOR: u128 = 10000;
+const BET_INTENT_DOMAIN: &[u8] = b"sports-program:bet-intent";
+const BET_QUOTE_DOMAIN: &[u8] = b"sports-program:bet-quote";
 
 #[derive(Accounts)]
 #[instruction(identifier: [u8; 32])]
@@ -29,7 +38,7 @@ pub struct InitClub<'info> {
     #[account(
         init_if_needed,
         payer = owner,
-        space = 8 + Club::SIZE,
+        space = 8 + Club::size(0),
         seeds = [
             b"club",
             admin.key().as_ref(),
@@ -64,6 +73,11 @@ pub struct InitClubEvent {
     pub token_mint: Pubkey,
 }
 
+pub(crate) fn split_prize(prize: u64, fee_point: u16) -> (u64, u64) {
+    let fee = (prize as u128 * fee_point as u128 / BASIS_DIVISOR) as u64;
+    (prize - fee, fee)
+}
+
 pub(crate) fn _init_club(ctx: Context<InitClub>, identifier: [u8; 32]) -> Result<()> {
     if ctx.accounts.club.initialized {
         return Ok(());
@@ -75,6 +89,14 @@ pub(crate) fn _init_club(ctx: Context<InitClub>, identifier: [u8; 32]) -> Result
     ctx.accounts.club.owner = ctx.accounts.owner.key();
     ctx.accounts.club.token_mint = ctx.accounts.token_mint.key();
     ctx.accounts.club.identifier = identifier;
+    ctx.accounts.club.min_odds = DEFAULT_MIN_ODDS;
+    ctx.accounts.club.max_odds = DEFAULT_MAX_ODDS;
+    ctx.accounts.club.min_stake = 1;
+    ctx.accounts.club.max_stake = u64::MAX;
+    ctx.accounts.club.max_game_liability = u64::MAX;
+    ctx.accounts.club.max_outcome_liability = u64::MAX;
+    ctx.accounts.club.max_player_liability = u64::MAX;
+    ctx.accounts.club.accept_admin_operators = true;
 
     let cpi_ctx = CpiContext::new(
         ctx.accounts.system_program.to_account_info(),
@@ -83,7 +105,7 @@ pub(crate) fn _init_club(ctx: Context<InitClub>, identifier: [u8; 32]) -> Result
             to: ctx.accounts.fee_receiver.to_account_info(),
         },
     );
-    send(cpi_ctx, CLUB_CREATION_FEE)?;
+    send(cpi_ctx, ctx.accounts.admin.club_creation_fee)?;
 
     emit!(InitClubEvent {
         owner: ctx.accounts.owner.key(),
@@ -236,7 +258,14 @@ pub(crate) fn _deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
 pub struct Withdraw<'info> {
     pub owner: Signer<'info>,
     // program accounts
-    #[account(mut, has_one = owner, has_one = token_mint)]
+    pub admin: Account<'info, Admin>,
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = owner,
+        has_one = token_mint,
+        constraint = !club.is_paused(&admin) @ SportsError::Paused,
+    )]
     pub club: Account<'info, Club>,
     #[account(seeds = [b"authority", club.key().as_ref()], bump)]
     pub club_authority: SystemAccount<'info>,
@@ -297,21 +326,265 @@ pub(crate) fn _withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
 }
 
 #[derive(Accounts)]
-#[instruction(identifier: [u8; 32])]
+pub struct AssignClubOperator<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub operator: SystemAccount<'info>,
+    // program accounts
+    #[account(
+        mut,
+        has_one = owner,
+        realloc = 8 + club.size_with_operator(operator.key),
+        realloc::payer = owner,
+        realloc::zero = false,
+    )]
+    pub club: Account<'info, Club>,
+    // system program
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct AssignClubOperatorEvent {
+    pub club: Pubkey,
+    pub operator: Pubkey,
+    pub permissions: u8,
+}
+
+pub(crate) fn _assign_club_operator(ctx: Context<AssignClubOperator>, permissions: u8) -> Result<()> {
+    ctx.accounts.club.assign_operator(ctx.accounts.operator.key(), permissions)?;
+
+    emit!(AssignClubOperatorEvent {
+        club: ctx.accounts.club.key(),
+        operator: ctx.accounts.operator.key(),
+        permissions,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveClubOperator<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub operator: SystemAccount<'info>,
+    // program accounts
+    #[account(
+        mut,
+        has_one = owner,
+        realloc = 8 + club.size_without_operator(operator.key),
+        realloc::payer = owner,
+        realloc::zero = false,
+    )]
+    pub club: Account<'info, Club>,
+    // system program
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct RemoveClubOperatorEvent {
+    pub club: Pubkey,
+    pub operator: Pubkey,
+}
+
+pub(crate) fn _remove_club_operator(ctx: Context<RemoveClubOperator>) -> Result<()> {
+    ctx.accounts.club.remove_operator(ctx.accounts.operator.key);
+
+    emit!(RemoveClubOperatorEvent {
+        club: ctx.accounts.club.key(),
+        operator: ctx.accounts.operator.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAcceptAdminOperators<'info> {
+    pub owner: Signer<'info>,
+    // program accounts
+    #[account(mut, has_one = owner)]
+    pub club: Account<'info, Club>,
+}
+
+#[event]
+pub struct SetAcceptAdminOperatorsEvent {
+    pub club: Pubkey,
+    pub accept: bool,
+}
+
+pub(crate) fn _set_accept_admin_operators(
+    ctx: Context<SetAcceptAdminOperators>,
+    accept: bool,
+) -> Result<()> {
+    ctx.accounts.club.accept_admin_operators = accept;
+
+    emit!(SetAcceptAdminOperatorsEvent {
+        club: ctx.accounts.club.key(),
+        accept,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetClubPaused<'info> {
+    #[account(
+        constraint = authority.key() == club.owner || admin.can_pause(authority.key)
+            @ SportsError::InvalidGuardian,
+    )]
+    pub authority: Signer<'info>,
+    // program accounts
+    pub admin: Account<'info, Admin>,
+    #[account(mut, has_one = admin)]
+    pub club: Account<'info, Club>,
+}
+
+#[event]
+pub struct SetClubPausedEvent {
+    pub club: Pubkey,
+    pub authority: Pubkey,
+    pub paused: bool,
+    pub guardian: bool,
+}
+
+pub(crate) fn _set_club_paused(ctx: Context<SetClubPaused>, paused: bool) -> Result<()> {
+    ctx.accounts.club.set_paused(&ctx.accounts.admin, ctx.accounts.authority.key, paused);
+
+    emit!(SetClubPausedEvent {
+        club: ctx.accounts.club.key(),
+        authority: ctx.accounts.authority.key(),
+        paused,
+        guardian: ctx.accounts.admin.can_pause(ctx.accounts.authority.key),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetBetLimits<'info> {
+    pub owner: Signer<'info>,
+    // program accounts
+    #[account(mut, has_one = owner)]
+    pub club: Account<'info, Club>,
+}
+
+#[event]
+pub struct SetBetLimitsEvent {
+    pub club: Pubkey,
+    pub min_odds: u64,
+    pub max_odds: u64,
+    pub min_stake: u64,
+    pub max_stake: u64,
+}
+
+pub(crate) fn _set_bet_limits(
+    ctx: Context<SetBetLimits>,
+    min_odds: u64,
+    max_odds: u64,
+    min_stake: u64,
+    max_stake: u64,
+) -> Result<()> {
+    ctx.accounts.club.set_bet_limits(min_odds, max_odds, min_stake, max_stake)?;
+
+    emit!(SetBetLimitsEvent {
+        club: ctx.accounts.club.key(),
+        min_odds,
+        max_odds,
+        min_stake,
+        max_stake,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetLiabilityCaps<'info> {
+    pub owner: Signer<'info>,
+    // program accounts
+    #[account(mut, has_one = owner)]
+    pub club: Account<'info, Club>,
+}
+
+#[event]
+pub struct SetLiabilityCapsEvent {
+    pub club: Pubkey,
+    pub max_game_liability: u64,
+    pub max_outcome_liability: u64,
+    pub max_player_liability: u64,
+}
+
+pub(crate) fn _set_liability_caps(
+    ctx: Context<SetLiabilityCaps>,
+    max_game_liability: u64,
+    max_outcome_liability: u64,
+    max_player_liability: u64,
+) -> Result<()> {
+    ctx.accounts.club.set_liability_caps(
+        max_game_liability,
+        max_outcome_liability,
+        max_player_liability,
+    );
+
+    emit!(SetLiabilityCapsEvent {
+        club: ctx.accounts.club.key(),
+        max_game_liability,
+        max_outcome_liability,
+        max_player_liability,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPricingKey<'info> {
+    pub owner: Signer<'info>,
+    // program accounts
+    #[account(mut, has_one = owner)]
+    pub club: Account<'info, Club>,
+}
+
+#[event]
+pub struct SetPricingKeyEvent {
+    pub club: Pubkey,
+    pub pricing_key: Option<Pubkey>,
+}
+
+pub(crate) fn _set_pricing_key(ctx: Context<SetPricingKey>, pricing_key: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.club.pricing_key = pricing_key;
+
+    emit!(SetPricingKeyEvent {
+        club: ctx.accounts.club.key(),
+        pricing_key,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(
+    identifier: [u8; 32],
+    market: Market,
+    metadata: Option<GameMetadata>,
+    outcomes: u8,
+)]
 pub struct StartGame<'info> {
     #[account(
         mut,
-        constraint = admin.is_operator(operator.key) @ SportsError::InvalidOperator,
+        constraint = club.has_permission(&admin, operator.key, Permission::CreateGame)
+            @ SportsError::MissingCreateGamePermission,
     )]
     pub operator: Signer<'info>,
     // program accounts
     pub admin: Account<'info, Admin>,
-    #[account(mut, has_one = admin)]
+    #[account(
+        mut,
+        has_one = admin,
+        constraint = !club.is_paused(&admin) @ SportsError::Paused,
+    )]
     pub club: Account<'info, Club>,
     #[account(
         init,
         payer = operator,
-        space = 8 + Game::SIZE,
+        space = 8 + Game::size(outcomes),
         seeds = [
             b"game",
             club.key().as_ref(),
@@ -328,15 +601,53 @@ pub struct StartGame<'info> {
 pub struct StartGameEvent {
     pub club: Pubkey,
     pub game: Pubkey,
+    pub fixture: [u8; 32],
+    pub market_type: MarketType,
+    pub line: i32,
+    pub metadata: Option<GameMetadata>,
+    pub mode: GameMode,
+    pub outcomes: u8,
+    pub start_time: i64,
+    pub cutoff_time: i64,
+}
+
+pub(crate) fn _start_game(
+    ctx: Context<StartGame>,
+    identifier: [u8; 32],
+    market: Market,
+    metadata: Option<GameMetadata>,
+    outcomes: u8,
+    start_time: i64,
+    cutoff_time: i64,
+) -> Result<()> {
+    let game = Game::new(
+        ctx.accounts.club.key(),
+        identifier,
+        market,
+        metadata,
+        outcomes,
+        start_time,
+        cutoff_time,
+    )?;
+    start_game(ctx, game, GameMode::FixedOdds)
 }
 
-pub(crate) fn _start_game(ctx: Context<StartGame>, identifier: [u8; 32]) -> Result<()> {
-    ctx.accounts.game.club = ctx.accounts.club.key();
-    ctx.accounts.game.identifier = identifier;
+/// Stores a new game in `mode`, shared by every start instruction.
+pub(crate) fn start_game(ctx: Context<StartGame>, game: Game, mode: GameMode) -> Result<()> {
+    ctx.accounts.game.set_inner(game.with_mode(mode));
 
+    let game = &ctx.accounts.game;
     emit!(StartGameEvent {
         club: ctx.accounts.club.key(),
-        game: ctx.accounts.game.key(),
+        game: game.key(),
+        fixture: game.market.fixture,
+        market_type: game.market.market_type,
+        line: game.market.line,
+        metadata: game.metadata.clone(),
+        mode,
+        outcomes: game.locking.len() as u8,
+        start_time: game.start_time,
+        cutoff_time: game.cutoff_time,
     });
 
     Ok(())
@@ -347,7 +658,8 @@ pub(crate) fn _start_game(ctx: Context<StartGame>, identifier: [u8; 32]) -> Resu
 pub struct CloseGame<'info> {
     #[account(
         mut,
-        constraint = admin.is_operator(operator.key) @ SportsError::InvalidOperator,
+        constraint = club.has_permission(&admin, operator.key, Permission::CreateGame)
+            @ SportsError::MissingCreateGamePermission,
     )]
     pub operator: Signer<'info>,
     // program accounts
@@ -381,155 +693,931 @@ pub(crate) fn _close_game(ctx: Context<CloseGame>, _cancel: bool) -> Result<()>
 }
 
 #[derive(Accounts)]
-#[instruction(identifier: [u8; 32], direction: u8, stake: u64, lock: u64)]
-pub struct Bet<'info> {
-    pub player: Signer<'info>,
+pub struct SetGameMetadata<'info> {
     #[account(
-        mut,
-        constraint = admin.is_operator(operator.key) @ SportsError::InvalidOperator,
+        constraint = club.has_permission(&admin, operator.key, Permission::CreateGame)
+            @ SportsError::MissingCreateGamePermission,
     )]
     pub operator: Signer<'info>,
     // program accounts
     pub admin: Account<'info, Admin>,
-    #[account(mut, has_one = admin, has_one = token_mint)]
+    #[account(has_one = admin)]
     pub club: Account<'info, Club>,
-    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
-    pub club_authority: SystemAccount<'info>,
-    #[account(
-        mut,
-        has_one = club,
-        constraint = game.identifier == identifier,
-    )]
+    #[account(mut, has_one = club)]
     pub game: Account<'info, Game>,
-    #[account(
-        init,
-        payer = operator,
-        space = 8 + Credential::SIZE,
-        seeds = [
-            b"credential",
-            club.key().as_ref(),
-            player.key().as_ref(),
-            &identifier,
-        ],
-        bump,
-    )]
-    pub credential: Account<'info, Credential>,
-    // token accounts
-    pub token_mint: InterfaceAccount<'info, Mint>,
-    #[account(mut)]
-    pub player_token_account: InterfaceAccount<'info, TokenAccount>,
-    #[account(
-        mut,
-        associated_token::mint = token_mint,
-        associated_token::authority = club_authority,
-    )]
-    pub supply_token_account: InterfaceAccount<'info, TokenAccount>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Interface<'info, TokenInterface>,
-    // system program
-    pub system_program: Program<'info, System>,
 }
 
 #[event]
-pub struct BetEvent {
+pub struct SetGameMetadataEvent {
     pub club: Pubkey,
-    pub credential: Pubkey,
-    pub player: Pubkey,
-    pub direction: u8,
-    pub stake: u64,
-    pub lock: u64,
+    pub game: Pubkey,
+    pub metadata: Option<GameMetadata>,
 }
 
-pub(crate) fn _bet(
-    ctx: Context<Bet>,
-    identifier: [u8; 32],
-    direction: u8,
-    stake: u64,
-    lock: u64,
+pub(crate) fn _set_game_metadata(
+    ctx: Context<SetGameMetadata>,
+    metadata: Option<GameMetadata>,
 ) -> Result<()> {
-    let cpi_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        TransferChecked {
-            from: ctx.accounts.player_token_account.to_account_info(),
-            mint: ctx.accounts.token_mint.to_account_info(),
-            to: ctx.accounts.supply_token_account.to_account_info(),
-            authority: ctx.accounts.player.to_account_info(),
-        },
-    );
-    transfer_checked(cpi_ctx, stake, ctx.accounts.token_mint.decimals)?;
-
-    // update club
-    ctx.accounts.club.bet(direction, stake, lock)?;
-    // update game
-    ctx.accounts.game.bet(stake);
-    // initialize credential
-    ctx.accounts.credential.club = ctx.accounts.club.key();
-    ctx.accounts.credential.player = ctx.accounts.player.key();
-    ctx.accounts.credential.identifier = identifier;
-    ctx.accounts.credential.direction = direction;
-    ctx.accounts.credential.stake = stake;
-    ctx.accounts.credential.lock = lock;
+    ctx.accounts.game.set_metadata(metadata.clone(), Clock::get()?.unix_timestamp)?;
 
-    emit!(BetEvent {
+    emit!(SetGameMetadataEvent {
         club: ctx.accounts.club.key(),
-        credential: ctx.accounts.credential.key(),
-        player: ctx.accounts.player.key(),
-        direction,
-        stake,
-        lock,
+        game: ctx.accounts.game.key(),
+        metadata,
     });
 
     Ok(())
 }
 
 #[derive(Accounts)]
-pub struct CloseBet<'info> {
-    pub player: Signer<'info>,
+pub struct SuspendGame<'info> {
     #[account(
-        mut,
-        constraint = admin.is_operator(operator.key) @ SportsError::InvalidOperator,
+        constraint = club.has_permission(&admin, operator.key, Permission::CreateGame)
+            @ SportsError::MissingCreateGamePermission,
     )]
     pub operator: Signer<'info>,
     // program accounts
     pub admin: Account<'info, Admin>,
-    #[account(mut, has_one = admin, has_one = token_mint)]
+    #[account(has_one = admin)]
     pub club: Account<'info, Club>,
-    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
-    pub club_authority: SystemAccount<'info>,
-    #[account(
-        mut,
-        has_one = club,
-        constraint = game.identifier == credential.identifier @ SportsError::InvalidIdentifier,
-    )]
+    #[account(mut, has_one = club)]
     pub game: Account<'info, Game>,
-    #[account(mut, close = operator, has_one = club, has_one = player)]
-    pub credential: Account<'info, Credential>,
-    // token accounts
-    pub token_mint: InterfaceAccount<'info, Mint>,
-    #[account(mut)]
-    pub player_token_account: InterfaceAccount<'info, TokenAccount>,
-    #[account(
-        mut,
-        associated_token::mint = token_mint,
-        associated_token::authority = club_authority,
+}
+
+#[event]
+pub struct SuspendGameEvent {
+    pub club: Pubkey,
+    pub game: Pubkey,
+}
+
+pub(crate) fn _suspend_game(ctx: Context<SuspendGame>) -> Result<()> {
+    ctx.accounts.game.suspend()?;
+
+    emit!(SuspendGameEvent {
+        club: ctx.accounts.club.key(),
+        game: ctx.accounts.game.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResumeGame<'info> {
+    #[account(
+        constraint = club.has_permission(&admin, operator.key, Permission::CreateGame)
+            @ SportsError::MissingCreateGamePermission,
+    )]
+    pub operator: Signer<'info>,
+    // program accounts
+    pub admin: Account<'info, Admin>,
+    #[account(has_one = admin)]
+    pub club: Account<'info, Club>,
+    #[account(mut, has_one = club)]
+    pub game: Account<'info, Game>,
+}
+
+#[event]
+pub struct ResumeGameEvent {
+    pub club: Pubkey,
+    pub game: Pubkey,
+}
+
+pub(crate) fn _resume_game(ctx: Context<ResumeGame>) -> Result<()> {
+    ctx.accounts.game.resume()?;
+
+    emit!(ResumeGameEvent {
+        club: ctx.accounts.club.key(),
+        game: ctx.accounts.game.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelGame<'info> {
+    #[account(
+        constraint = club.has_permission(&admin, operator.key, Permission::Void)
+            @ SportsError::MissingVoidPermission,
+    )]
+    pub operator: Signer<'info>,
+    // program accounts
+    pub admin: Account<'info, Admin>,
+    #[account(mut, has_one = admin)]
+    pub club: Account<'info, Club>,
+    #[account(mut, has_one = club)]
+    pub game: Account<'info, Game>,
+}
+
+#[event]
+pub struct CancelGameEvent {
+    pub club: Pubkey,
+    pub game: Pubkey,
+}
+
+pub(crate) fn _cancel_game(ctx: Context<CancelGame>) -> Result<()> {
+    ctx.accounts.club.cancel_game(&mut ctx.accounts.game)?;
+
+    emit!(CancelGameEvent {
+        club: ctx.accounts.club.key(),
+        game: ctx.accounts.game.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(identifier: [u8; 32], direction: u8, stake: u64, lock: u64)]
+pub struct Bet<'info> {
+    pub player: Signer<'info>,
+    #[account(
+        mut,
+        constraint = club.has_permission(&admin, operator.key, Permission::AcceptBet)
+            @ SportsError::MissingAcceptBetPermission,
+    )]
+    pub operator: Signer<'info>,
+    // program accounts
+    pub admin: Account<'info, Admin>,
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = token_mint,
+        constraint = !club.is_paused(&admin) @ SportsError::Paused,
+    )]
+    pub club: Account<'info, Club>,
+    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
+    pub club_authority: SystemAccount<'info>,
+    #[account(
+        mut,
+        has_one = club,
+        constraint = game.identifier == identifier,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = 8 + Position::SIZE,
+        seeds = [
+            b"position",
+            game.key().as_ref(),
+            player.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + Credential::SIZE,
+        seeds = [
+            b"credential",
+            club.key().as_ref(),
+            player.key().as_ref(),
+            &identifier,
+            &position.bets.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub credential: Account<'info, Credential>,
+    // token accounts
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub player_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = club_authority,
+    )]
+    pub supply_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    // system program
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct BetEvent {
+    pub club: Pubkey,
+    pub credential: Pubkey,
+    pub player: Pubkey,
+    pub direction: u8,
+    pub stake: u64,
+    pub lock: u64,
+}
+
+pub(crate) fn _bet(
+    ctx: Context<Bet>,
+    identifier: [u8; 32],
+    direction: u8,
+    stake: u64,
+    lock: u64,
+) -> Result<()> {
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.player_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.supply_token_account.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        },
+    );
+    transfer_checked(cpi_ctx, stake, ctx.accounts.token_mint.decimals)?;
+
+    // update club, game and position
+    let index = ctx.accounts.position.bets;
+    ctx.accounts.position.open(
+        ctx.accounts.game.key(),
+        ctx.accounts.player.key(),
+        ctx.accounts.operator.key(),
+    );
+    ctx.accounts.club.bet(
+        &mut ctx.accounts.game,
+        &mut ctx.accounts.position,
+        direction,
+        stake,
+        lock,
+        Clock::get()?.unix_timestamp,
+    )?;
+    // initialize credential
+    ctx.accounts.credential.club = ctx.accounts.club.key();
+    ctx.accounts.credential.player = ctx.accounts.player.key();
+    ctx.accounts.credential.payer = ctx.accounts.operator.key();
+    ctx.accounts.credential.index = index;
+    ctx.accounts.credential.identifier = identifier;
+    ctx.accounts.credential.direction = direction;
+    ctx.accounts.credential.stake = stake;
+    ctx.accounts.credential.lock = lock;
+
+    emit!(BetEvent {
+        club: ctx.accounts.club.key(),
+        credential: ctx.accounts.credential.key(),
+        player: ctx.accounts.player.key(),
+        direction,
+        stake,
+        lock,
+    });
+
+    Ok(())
+}
+
+/// Bet signed off-chain by the player and submitted by an operator.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BetIntent {
+    pub club: Pubkey,
+    pub identifier: [u8; 32],
+    pub direction: u8,
+    pub stake: u64,
+    pub min_lock: u64,
+    pub expiry: i64,
+    pub nonce: u64,
+}
+
+impl BetIntent {
+    fn message(&self) -> Result<Vec<u8>> {
+        let mut message = BET_INTENT_DOMAIN.to_vec();
+        self.serialize(&mut message)?;
+        Ok(message)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(intent: BetIntent)]
+pub struct BetWithIntent<'info> {
+    pub player: SystemAccount<'info>,
+    #[account(
+        mut,
+        constraint = club.has_permission(&admin, operator.key, Permission::AcceptBet)
+            @ SportsError::MissingAcceptBetPermission,
+    )]
+    pub operator: Signer<'info>,
+    // program accounts
+    pub admin: Box<Account<'info, Admin>>,
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = token_mint,
+        constraint = !club.is_paused(&admin) @ SportsError::Paused,
+        constraint = club.key() == intent.club @ SportsError::InvalidSignature,
+    )]
+    pub club: Box<Account<'info, Club>>,
+    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
+    pub club_authority: SystemAccount<'info>,
+    #[account(
+        mut,
+        has_one = club,
+        constraint = game.identifier == intent.identifier @ SportsError::InvalidIdentifier,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = 8 + Position::SIZE,
+        seeds = [
+            b"position",
+            game.key().as_ref(),
+            player.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = 8 + IntentNonce::SIZE,
+        seeds = [
+            b"nonce",
+            club.key().as_ref(),
+            player.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub intent_nonce: Box<Account<'info, IntentNonce>>,
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + Credential::SIZE,
+        seeds = [
+            b"credential",
+            club.key().as_ref(),
+            player.key().as_ref(),
+            &intent.identifier,
+            &position.bets.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub credential: Box<Account<'info, Credential>>,
+    // token accounts
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    // the player must have approved `club_authority` as delegate
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = player,
+    )]
+    pub player_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = club_authority,
+    )]
+    pub supply_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: instructions sysvar
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    // system program
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn _bet_with_intent(
+    ctx: Context<BetWithIntent>,
+    intent: BetIntent,
+    lock: u64,
+) -> Result<()> {
+    verify_ed25519_signature(
+        &ctx.accounts.instructions,
+        ctx.accounts.player.key,
+        &intent.message()?,
+    )?;
+    let timestamp = Clock::get()?.unix_timestamp;
+    require_gt!(intent.expiry, timestamp, SportsError::IntentExpired);
+    require_gte!(lock, intent.min_lock, SportsError::LockBelowMinimum);
+
+    // consume nonce
+    ctx.accounts.intent_nonce.club = ctx.accounts.club.key();
+    ctx.accounts.intent_nonce.player = ctx.accounts.player.key();
+    ctx.accounts.intent_nonce.consume(intent.nonce)?;
+
+    let club = ctx.accounts.club.key();
+    let bumps = [ctx.bumps.club_authority];
+    let signer_seeds = &[
+        &[
+            b"authority".as_slice(),
+            club.as_ref(),
+            &bumps,
+        ][..],
+    ];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.player_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.supply_token_account.to_account_info(),
+            authority: ctx.accounts.club_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    transfer_checked(cpi_ctx, intent.stake, ctx.accounts.token_mint.decimals)?;
+
+    // update club, game and position
+    let index = ctx.accounts.position.bets;
+    ctx.accounts.position.open(
+        ctx.accounts.game.key(),
+        ctx.accounts.player.key(),
+        ctx.accounts.operator.key(),
+    );
+    ctx.accounts.club.bet(
+        &mut ctx.accounts.game,
+        &mut ctx.accounts.position,
+        intent.direction,
+        intent.stake,
+        lock,
+        timestamp,
+    )?;
+    // initialize credential
+    ctx.accounts.credential.club = ctx.accounts.club.key();
+    ctx.accounts.credential.player = ctx.accounts.player.key();
+    ctx.accounts.credential.payer = ctx.accounts.operator.key();
+    ctx.accounts.credential.index = index;
+    ctx.accounts.credential.identifier = intent.identifier;
+    ctx.accounts.credential.direction = intent.direction;
+    ctx.accounts.credential.stake = intent.stake;
+    ctx.accounts.credential.lock = lock;
+
+    emit!(BetEvent {
+        club: ctx.accounts.club.key(),
+        credential: ctx.accounts.credential.key(),
+        player: ctx.accounts.player.key(),
+        direction: intent.direction,
+        stake: intent.stake,
+        lock,
+    });
+
+    Ok(())
+}
+
+/// Price signed off-chain by the club pricing key. `odds` are decimal odds
+/// scaled by 10000, so the lock of a bet is `stake * odds / 10000`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BetQuote {
+    pub club: Pubkey,
+    pub player: Pubkey,
+    // unique per quote, keys the stake filled against it
+    pub nonce: u64,
+    pub identifier: [u8; 32],
+    pub direction: u8,
+    pub odds: u64,
+    pub max_stake: u64,
+    pub expiry_slot: u64,
+}
+
+impl BetQuote {
+    fn message(&self) -> Result<Vec<u8>> {
+        let mut message = BET_QUOTE_DOMAIN.to_vec();
+        self.serialize(&mut message)?;
+        Ok(message)
+    }
+
+    fn lock(&self, stake: u64) -> Result<u64> {
+        let lock = stake as u128 * self.odds as u128 / ODDS_DIVISOR as u128;
+        u64::try_from(lock).map_err(|_| SportsError::InvalidLockAmount.into())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(quote: BetQuote)]
+pub struct BetWithQuote<'info> {
+    #[account(
+        mut,
+        constraint = player.key() == quote.player @ SportsError::InvalidSignature,
+    )]
+    pub player: Signer<'info>,
+    // program accounts
+    pub admin: Box<Account<'info, Admin>>,
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = token_mint,
+        constraint = !club.is_paused(&admin) @ SportsError::Paused,
+        constraint = club.key() == quote.club @ SportsError::InvalidSignature,
+    )]
+    pub club: Box<Account<'info, Club>>,
+    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
+    pub club_authority: SystemAccount<'info>,
+    #[account(
+        mut,
+        has_one = club,
+        constraint = game.identifier == quote.identifier @ SportsError::InvalidIdentifier,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + Position::SIZE,
+        seeds = [
+            b"position",
+            game.key().as_ref(),
+            player.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + QuoteFill::SIZE,
+        seeds = [
+            b"quote",
+            club.key().as_ref(),
+            &quote.nonce.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub quote_fill: Box<Account<'info, QuoteFill>>,
+    #[account(
+        init,
+        payer = player,
+        space = 8 + Credential::SIZE,
+        seeds = [
+            b"credential",
+            club.key().as_ref(),
+            player.key().as_ref(),
+            &quote.identifier,
+            &position.bets.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub credential: Box<Account<'info, Credential>>,
+    // token accounts
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub player_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = club_authority,
+    )]
+    pub supply_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: instructions sysvar
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    // system program
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn _bet_with_quote(
+    ctx: Context<BetWithQuote>,
+    quote: BetQuote,
+    stake: u64,
+    min_lock: u64,
+) -> Result<()> {
+    let pricing_key = ctx.accounts.club.pricing_key.ok_or(SportsError::PricingKeyNotSet)?;
+    verify_ed25519_signature(&ctx.accounts.instructions, &pricing_key, &quote.message()?)?;
+    let clock = Clock::get()?;
+    require_gte!(quote.expiry_slot, clock.slot, SportsError::QuoteExpired);
+    // track the stake filled so a quote never prices more than max_stake
+    ctx.accounts.quote_fill.club = ctx.accounts.club.key();
+    ctx.accounts.quote_fill.payer = ctx.accounts.player.key();
+    ctx.accounts.quote_fill.nonce = quote.nonce;
+    ctx.accounts.quote_fill.expiry_slot = quote.expiry_slot;
+    ctx.accounts.quote_fill.fill(stake, quote.max_stake)?;
+    let lock = quote.lock(stake)?;
+    require_gte!(lock, min_lock, SportsError::LockBelowMinimum);
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.player_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.supply_token_account.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        },
+    );
+    transfer_checked(cpi_ctx, stake, ctx.accounts.token_mint.decimals)?;
+
+    // update club, game and position
+    let index = ctx.accounts.position.bets;
+    ctx.accounts.position.open(
+        ctx.accounts.game.key(),
+        ctx.accounts.player.key(),
+        ctx.accounts.player.key(),
+    );
+    ctx.accounts.club.bet(
+        &mut ctx.accounts.game,
+        &mut ctx.accounts.position,
+        quote.direction,
+        stake,
+        lock,
+        clock.unix_timestamp,
+    )?;
+    // initialize credential
+    ctx.accounts.credential.club = ctx.accounts.club.key();
+    ctx.accounts.credential.player = ctx.accounts.player.key();
+    ctx.accounts.credential.payer = ctx.accounts.player.key();
+    ctx.accounts.credential.index = index;
+    ctx.accounts.credential.identifier = quote.identifier;
+    ctx.accounts.credential.direction = quote.direction;
+    ctx.accounts.credential.stake = stake;
+    ctx.accounts.credential.lock = lock;
+
+    emit!(BetEvent {
+        club: ctx.accounts.club.key(),
+        credential: ctx.accounts.credential.key(),
+        player: ctx.accounts.player.key(),
+        direction: quote.direction,
+        stake,
+        lock,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseQuoteFill<'info> {
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+    // program accounts
+    #[account(mut, close = payer, has_one = payer)]
+    pub quote_fill: Account<'info, QuoteFill>,
+}
+
+#[event]
+pub struct CloseQuoteFillEvent {
+    pub club: Pubkey,
+    pub quote_fill: Pubkey,
+    pub nonce: u64,
+    pub filled: u64,
+}
+
+/// Returns the rent of a quote fill once its quote can no longer be used.
+pub(crate) fn _close_quote_fill(ctx: Context<CloseQuoteFill>) -> Result<()> {
+    require!(
+        ctx.accounts.quote_fill.is_expired(Clock::get()?.slot),
+        SportsError::QuoteNotExpired,
+    );
+
+    emit!(CloseQuoteFillEvent {
+        club: ctx.accounts.quote_fill.club,
+        quote_fill: ctx.accounts.quote_fill.key(),
+        nonce: ctx.accounts.quote_fill.nonce,
+        filled: ctx.accounts.quote_fill.filled,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseBet<'info> {
+    pub player: Signer<'info>,
+    #[account(
+        constraint = club.has_permission(&admin, operator.key, Permission::Void)
+            @ SportsError::MissingVoidPermission,
+    )]
+    pub operator: Signer<'info>,
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+    // program accounts
+    pub admin: Account<'info, Admin>,
+    #[account(mut, has_one = admin, has_one = token_mint)]
+    pub club: Account<'info, Club>,
+    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
+    pub club_authority: SystemAccount<'info>,
+    #[account(
+        mut,
+        has_one = club,
+        constraint = game.identifier == credential.identifier @ SportsError::InvalidIdentifier,
+        constraint = !game.is_finalized() @ SportsError::GameFinalized,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [
+            b"position",
+            game.key().as_ref(),
+            player.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+    #[account(mut, close = payer, has_one = club, has_one = player, has_one = payer)]
+    pub credential: Account<'info, Credential>,
+    // token accounts
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub player_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = club_authority,
+    )]
+    pub supply_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct CloseBetEvent {
+    pub club: Pubkey,
+    pub credential: Pubkey,
+    pub player: Pubkey,
+}
+
+pub(crate) fn _close_bet(ctx: Context<CloseBet>) -> Result<()> {
+    // update club, game and position
+    ctx.accounts.club.close_bet(
+        &mut ctx.accounts.game,
+        &mut ctx.accounts.position,
+        &ctx.accounts.credential,
+    )?;
+
+    let club = ctx.accounts.club.key();
+    let bumps = [ctx.bumps.club_authority];
+    let signer_seeds = &[
+        &[
+            b"authority".as_slice(),
+            club.as_ref(),
+            &bumps,
+        ][..],
+    ];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.supply_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.player_token_account.to_account_info(),
+            authority: ctx.accounts.club_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    transfer_checked(cpi_ctx, ctx.accounts.credential.stake, ctx.accounts.token_mint.decimals)?;
+
+    emit!(CloseBetEvent {
+        club: ctx.accounts.club.key(),
+        credential: ctx.accounts.credential.key(),
+        player: ctx.accounts.player.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CashOut<'info> {
+    pub player: Signer<'info>,
+    #[account(
+        constraint = club.has_permission(&admin, operator.key, Permission::AcceptBet)
+            @ SportsError::MissingAcceptBetPermission,
+    )]
+    pub operator: Signer<'info>,
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+    // program accounts
+    pub admin: Box<Account<'info, Admin>>,
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = token_mint,
+        constraint = !club.is_paused(&admin) @ SportsError::Paused,
+    )]
+    pub club: Box<Account<'info, Club>>,
+    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
+    pub club_authority: SystemAccount<'info>,
+    #[account(
+        mut,
+        has_one = club,
+        constraint = game.identifier == credential.identifier @ SportsError::InvalidIdentifier,
+        constraint = !game.is_finalized() @ SportsError::GameFinalized,
+        constraint = !game.is_cancelled() @ SportsError::InvalidGameStatus,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        mut,
+        seeds = [
+            b"position",
+            game.key().as_ref(),
+            player.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+    #[account(mut, has_one = club, has_one = player, has_one = payer)]
+    pub credential: Box<Account<'info, Credential>>,
+    // token accounts
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub player_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = club_authority,
+    )]
+    pub supply_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct CashOutEvent {
+    pub club: Pubkey,
+    pub credential: Pubkey,
+    pub player: Pubkey,
+    pub stake: u64,
+    pub lock: u64,
+    pub amount: u64,
+    pub remaining_stake: u64,
+}
+
+pub(crate) fn _cash_out(ctx: Context<CashOut>, stake: u64, amount: u64) -> Result<()> {
+    // update club, game, position and credential
+    let lock = ctx.accounts.club.cash_out(
+        &mut ctx.accounts.game,
+        &mut ctx.accounts.position,
+        &mut ctx.accounts.credential,
+        stake,
+        amount,
+    )?;
+
+    if amount > 0 {
+        let club = ctx.accounts.club.key();
+        let bumps = [ctx.bumps.club_authority];
+        let signer_seeds = &[
+            &[
+                b"authority".as_slice(),
+                club.as_ref(),
+                &bumps,
+            ][..],
+        ];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.supply_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.player_token_account.to_account_info(),
+                authority: ctx.accounts.club_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
+    }
+
+    emit!(CashOutEvent {
+        club: ctx.accounts.club.key(),
+        credential: ctx.accounts.credential.key(),
+        player: ctx.accounts.player.key(),
+        stake,
+        lock,
+        amount,
+        remaining_stake: ctx.accounts.credential.stake,
+    });
+
+    // fully cashed out
+    if ctx.accounts.credential.stake == 0 {
+        ctx.accounts.credential.close(ctx.accounts.payer.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VoidBets<'info> {
+    #[account(
+        constraint = club.has_permission(&admin, operator.key, Permission::Void)
+            @ SportsError::MissingVoidPermission,
     )]
-    pub supply_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub operator: Signer<'info>,
+    // program accounts
+    pub admin: Box<Account<'info, Admin>>,
+    #[account(mut, has_one = admin, has_one = token_mint)]
+    pub club: Box<Account<'info, Club>>,
+    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
+    pub club_authority: SystemAccount<'info>,
+    #[account(
+        mut,
+        has_one = club,
+        constraint = game.is_cancelled() @ SportsError::GameNotCancelled,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    // token accounts
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = club_authority,
+    )]
+    pub supply_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
+    // remaining accounts: [credential, player_token_account, payer, position] per bet
 }
 
 #[event]
-pub struct CloseBetEvent {
+pub struct VoidBetEvent {
     pub club: Pubkey,
+    pub game: Pubkey,
     pub credential: Pubkey,
     pub player: Pubkey,
+    pub stake: u64,
 }
 
-pub(crate) fn _close_bet(ctx: Context<CloseBet>) -> Result<()> {
-    // update club
-    ctx.accounts.club.close_bet(&ctx.accounts.credential)?;
-    // update game
-    ctx.accounts.game.close_bet(ctx.accounts.credential.stake);
+pub(crate) fn _void_bets<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, VoidBets<'info>>,
+) -> Result<()> {
+    let bets = ctx.remaining_accounts.chunks_exact(4);
+    require!(
+        bets.len() > 0 && bets.remainder().is_empty(),
+        SportsError::InvalidRemainingAccounts,
+    );
 
     let club = ctx.accounts.club.key();
     let bumps = [ctx.bumps.club_authority];
@@ -540,72 +1628,246 @@ pub(crate) fn _close_bet(ctx: Context<CloseBet>) -> Result<()> {
             &bumps,
         ][..],
     ];
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        TransferChecked {
-            from: ctx.accounts.supply_token_account.to_account_info(),
-            mint: ctx.accounts.token_mint.to_account_info(),
-            to: ctx.accounts.player_token_account.to_account_info(),
-            authority: ctx.accounts.club_authority.to_account_info(),
-        },
-        signer_seeds,
-    );
-    transfer_checked(cpi_ctx, ctx.accounts.credential.stake, ctx.accounts.token_mint.decimals)?;
 
-    emit!(CloseBetEvent {
-        club: ctx.accounts.club.key(),
-        credential: ctx.accounts.credential.key(),
-        player: ctx.accounts.player.key(),
-    });
+    for accounts in bets {
+        let credential = Account::<Credential>::try_from(&accounts[0])?;
+        require_keys_eq!(credential.club, club);
+        require_keys_eq!(credential.payer, accounts[2].key());
+        require!(
+            credential.identifier == ctx.accounts.game.identifier,
+            SportsError::InvalidIdentifier,
+        );
+        let player_token_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?;
+        require!(
+            player_token_account.owner == credential.player
+                && player_token_account.mint == ctx.accounts.token_mint.key(),
+            SportsError::InvalidPlayerTokenAccount,
+        );
+        let mut position = load_position(&accounts[3], &ctx.accounts.game, &credential)?;
+
+        // update club, game and position
+        ctx.accounts.club.close_bet(&mut ctx.accounts.game, &mut position, &credential)?;
+        position.exit(&crate::ID)?;
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.supply_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: player_token_account.to_account_info(),
+                authority: ctx.accounts.club_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, credential.stake, ctx.accounts.token_mint.decimals)?;
+
+        emit!(VoidBetEvent {
+            club,
+            game: ctx.accounts.game.key(),
+            credential: credential.key(),
+            player: credential.player,
+            stake: credential.stake,
+        });
+
+        credential.close(accounts[2].clone())?;
+    }
 
     Ok(())
 }
 
+/// Loads the position a remaining account claims to hold for `credential`.
+fn load_position<'info>(
+    info: &'info AccountInfo<'info>,
+    game: &Account<Game>,
+    credential: &Credential,
+) -> Result<Account<'info, Position>> {
+    let position = Account::<Position>::try_from(info)?;
+    require_keys_eq!(position.game, game.key());
+    require_keys_eq!(position.player, credential.player);
+
+    Ok(position)
+}
+
 #[derive(Accounts)]
-#[instruction(direction: u8)]
-pub struct Settle<'info> {
-    pub player: Signer<'info>,
+pub struct ClosePosition<'info> {
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+    // program accounts
+    /// CHECK: the position game, possibly closed already
+    pub game: UncheckedAccount<'info>,
     #[account(
         mut,
-        constraint = admin.is_operator(operator.key) @ SportsError::InvalidOperator,
+        close = payer,
+        has_one = game,
+        has_one = payer,
+        constraint = position.open_bets == 0 @ SportsError::PositionHasOpenBets,
+    )]
+    pub position: Account<'info, Position>,
+}
+
+#[event]
+pub struct ClosePositionEvent {
+    pub game: Pubkey,
+    pub position: Pubkey,
+    pub player: Pubkey,
+}
+
+pub(crate) fn _close_position(ctx: Context<ClosePosition>) -> Result<()> {
+    // a game still open could take new bets against this position
+    if ctx.accounts.game.owner == &crate::ID {
+        let game = Game::try_deserialize(&mut &ctx.accounts.game.data.borrow()[..])?;
+        require!(game.is_finalized() || game.is_cancelled(), SportsError::InvalidGameStatus);
+    }
+
+    emit!(ClosePositionEvent {
+        game: ctx.accounts.game.key(),
+        position: ctx.accounts.position.key(),
+        player: ctx.accounts.position.player,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PostResult<'info> {
+    #[account(
+        constraint = club.has_permission(&admin, operator.key, Permission::PostResult)
+            @ SportsError::MissingPostResultPermission,
     )]
     pub operator: Signer<'info>,
-    pub fee_receiver: SystemAccount<'info>,
     // program accounts
-    #[account(has_one = fee_receiver)]
     pub admin: Account<'info, Admin>,
-    #[account(mut, has_one = admin, has_one = token_mint)]
+    #[account(mut, has_one = admin)]
     pub club: Account<'info, Club>,
+    #[account(mut, has_one = club)]
+    pub game: Account<'info, Game>,
+}
+
+#[event]
+pub struct PostResultEvent {
+    pub club: Pubkey,
+    pub game: Pubkey,
+    pub operator: Pubkey,
+    pub direction: u8,
+    pub timestamp: i64,
+}
+
+pub(crate) fn _post_result(ctx: Context<PostResult>, direction: u8) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let settlements = ctx.accounts.game.single_winner(direction)?;
+    ctx.accounts.club.post_result(
+        &mut ctx.accounts.game,
+        settlements,
+        ctx.accounts.operator.key(),
+        timestamp,
+    )?;
+
+    emit!(PostResultEvent {
+        club: ctx.accounts.club.key(),
+        game: ctx.accounts.game.key(),
+        operator: ctx.accounts.operator.key(),
+        direction,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PostSettlementsEvent {
+    pub club: Pubkey,
+    pub game: Pubkey,
+    pub operator: Pubkey,
+    pub settlements: Vec<Settlement>,
+    pub timestamp: i64,
+}
+
+/// Posts a result settling each outcome separately, e.g. push or half-win
+/// results of handicap and totals markets.
+pub(crate) fn _post_settlements(
+    ctx: Context<PostResult>,
+    settlements: Vec<Settlement>,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    ctx.accounts.club.post_result(
+        &mut ctx.accounts.game,
+        settlements.clone(),
+        ctx.accounts.operator.key(),
+        timestamp,
+    )?;
+
+    emit!(PostSettlementsEvent {
+        club: ctx.accounts.club.key(),
+        game: ctx.accounts.game.key(),
+        operator: ctx.accounts.operator.key(),
+        settlements,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Settle<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub player: SystemAccount<'info>,
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+    pub fee_receiver: SystemAccount<'info>,
+    // program accounts
+    #[account(has_one = fee_receiver)]
+    pub admin: Box<Account<'info, Admin>>,
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = token_mint,
+        constraint = !club.is_settle_paused(&admin) @ SportsError::Paused,
+    )]
+    pub club: Box<Account<'info, Club>>,
     #[account(seeds = [b"authority", club.key().as_ref()], bump)]
     pub club_authority: SystemAccount<'info>,
     #[account(
+        mut,
+        has_one = club,
+        constraint = game.identifier == credential.identifier @ SportsError::InvalidIdentifier,
+        constraint = game.is_finalized() @ SportsError::GameNotFinalized,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        mut,
         seeds = [
-            b"game",
-            club.key().as_ref(),
-            &credential.identifier,
+            b"position",
+            game.key().as_ref(),
+            player.key().as_ref(),
         ],
         bump,
     )]
-    pub game: SystemAccount<'info>,
-    #[account(mut, close = operator, has_one = club, has_one = player)]
-    pub credential: Account<'info, Credential>,
+    pub position: Box<Account<'info, Position>>,
+    #[account(mut, close = payer, has_one = club, has_one = player, has_one = payer)]
+    pub credential: Box<Account<'info, Credential>>,
     // token accounts
-    pub token_mint: InterfaceAccount<'info, Mint>,
-    #[account(mut)]
-    pub player_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::mint = token_mint,
         associated_token::authority = club_authority,
     )]
-    pub supply_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub supply_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         init_if_needed,
-        payer = operator,
+        payer = cranker,
         associated_token::mint = token_mint,
         associated_token::authority = fee_receiver,
     )]
-    pub fee_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub fee_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
     // system program
@@ -619,18 +1881,25 @@ pub struct SettleEvent {
     pub player: Pubkey,
     pub bet_direction: u8,
     pub final_direction: u8,
+    pub settlement: Settlement,
+    pub prize: u64,
+    pub refund: u64,
+    pub fee: u64,
 }
 
-pub(crate) fn _settle(ctx: Context<Settle>, direction: u8) -> Result<()> {
-    // update club
-    let win = direction == ctx.accounts.credential.direction;
-    ctx.accounts.club.settle(win, &ctx.accounts.credential)?;
+pub(crate) fn _settle(ctx: Context<Settle>) -> Result<()> {
+    // update club, game and position
+    let (prize, refund) = ctx.accounts.club.settle(
+        &mut ctx.accounts.game,
+        &mut ctx.accounts.position,
+        &ctx.accounts.credential,
+    )?;
 
-    if win {
-        let prize = ctx.accounts.credential.lock;
-        let fee = (prize as u128 * SETTLE_FEE_POINT / BASIS_DIVISOR) as u64;
-        let receiving = prize - fee;
+    // fixed odds bets are charged on their net winnings, pool shares as a whole
+    let fee_point = ctx.accounts.admin.settle_fee_point(&ctx.accounts.club);
+    let (receiving, fee) = split_prize(prize, fee_point);
 
+    if prize + refund > 0 {
         let club = ctx.accounts.club.key();
         let bumps = [ctx.bumps.club_authority];
         let signer_seeds = &[
@@ -663,7 +1932,7 @@ pub(crate) fn _settle(ctx: Context<Settle>, direction: u8) -> Result<()> {
             },
             signer_seeds,
         );
-        transfer_checked(cpi_ctx, receiving, ctx.accounts.token_mint.decimals)?;
+        transfer_checked(cpi_ctx, receiving + refund, ctx.accounts.token_mint.decimals)?;
     }
 
     emit!(SettleEvent {
@@ -671,8 +1940,173 @@ pub(crate) fn _settle(ctx: Context<Settle>, direction: u8) -> Result<()> {
         credential: ctx.accounts.credential.key(),
         player: ctx.accounts.player.key(),
         bet_direction: ctx.accounts.credential.direction,
-        final_direction: direction,
+        final_direction: ctx.accounts.game.final_direction,
+        settlement: ctx.accounts.game.settlements[ctx.accounts.credential.direction as usize],
+        prize,
+        refund,
+        fee,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SettleBatch<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub fee_receiver: SystemAccount<'info>,
+    // program accounts
+    #[account(has_one = fee_receiver)]
+    pub admin: Box<Account<'info, Admin>>,
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = token_mint,
+        constraint = !club.is_settle_paused(&admin) @ SportsError::Paused,
+    )]
+    pub club: Box<Account<'info, Club>>,
+    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
+    pub club_authority: SystemAccount<'info>,
+    #[account(
+        mut,
+        has_one = club,
+        constraint = game.is_finalized() @ SportsError::GameNotFinalized,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    // token accounts
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = club_authority,
+    )]
+    pub supply_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = token_mint,
+        associated_token::authority = fee_receiver,
+    )]
+    pub fee_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    // system program
+    pub system_program: Program<'info, System>,
+    // remaining accounts: [credential, player_token_account, payer, position] per bet
+}
+
+#[event]
+pub struct SettleBatchEvent {
+    pub club: Pubkey,
+    pub game: Pubkey,
+    pub final_direction: u8,
+    pub settled: u32,
+    pub winners: u32,
+    pub prize: u64,
+    pub refund: u64,
+    pub fee: u64,
+}
+
+pub(crate) fn _settle_batch<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SettleBatch<'info>>,
+) -> Result<()> {
+    let bets = ctx.remaining_accounts.chunks_exact(4);
+    require!(
+        bets.len() > 0 && bets.remainder().is_empty(),
+        SportsError::InvalidRemainingAccounts,
+    );
+
+    let club = ctx.accounts.club.key();
+    let bumps = [ctx.bumps.club_authority];
+    let signer_seeds = &[
+        &[
+            b"authority".as_slice(),
+            club.as_ref(),
+            &bumps,
+        ][..],
+    ];
+
+    let fee_point = ctx.accounts.admin.settle_fee_point(&ctx.accounts.club);
+    let mut settled = 0;
+    let mut winners = 0;
+    let mut total_prize = 0;
+    let mut total_refund = 0;
+    let mut total_fee = 0;
+    for accounts in bets {
+        let credential = Account::<Credential>::try_from(&accounts[0])?;
+        require_keys_eq!(credential.club, club);
+        require_keys_eq!(credential.payer, accounts[2].key());
+        require!(
+            credential.identifier == ctx.accounts.game.identifier,
+            SportsError::InvalidIdentifier,
+        );
+        let mut position = load_position(&accounts[3], &ctx.accounts.game, &credential)?;
+
+        // update club, game and position
+        let (prize, refund) = ctx.accounts.club.settle(
+            &mut ctx.accounts.game,
+            &mut position,
+            &credential,
+        )?;
+        position.exit(&crate::ID)?;
+        settled += 1;
+
+        if prize + refund > 0 {
+            let player_token_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?;
+            require!(
+                player_token_account.owner == credential.player
+                    && player_token_account.mint == ctx.accounts.token_mint.key(),
+                SportsError::InvalidPlayerTokenAccount,
+            );
+
+            let (receiving, fee) = split_prize(prize, fee_point);
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.supply_token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: player_token_account.to_account_info(),
+                    authority: ctx.accounts.club_authority.to_account_info(),
+                },
+                signer_seeds,
+            );
+            transfer_checked(cpi_ctx, receiving + refund, ctx.accounts.token_mint.decimals)?;
+
+            if prize > 0 {
+                winners += 1;
+            }
+            total_prize += prize;
+            total_refund += refund;
+            total_fee += fee;
+        }
+
+        credential.close(accounts[2].clone())?;
+    }
+
+    if total_fee > 0 {
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.supply_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.fee_token_account.to_account_info(),
+                authority: ctx.accounts.club_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, total_fee, ctx.accounts.token_mint.decimals)?;
+    }
+
+    emit!(SettleBatchEvent {
+        club,
+        game: ctx.accounts.game.key(),
+        final_direction: ctx.accounts.game.final_direction,
+        settled,
+        winners,
+        prize: total_prize,
+        refund: total_refund,
+        fee: total_fee,
     });
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,6 +1,15 @@
 mod admin;
+mod exchange;
 mod game;
+mod parlay;
+mod pool;
 
 pub use admin::*;
 
-pub use game::*;
\ No newline at end of file
+pub use exchange::*;
+
+pub use game::*;
+
+pub use parlay::*;
+
+pub use pool::*;
@@ -0,0 +1,311 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked},
+};
+
+use crate::{
+    state::{
+        Admin, Club, Game, Parlay, ParlayLeg, Permission,
+        MIN_PARLAY_LEGS, MAX_PARLAY_LEGS,
+    },
+    error::SportsError,
+};
+use super::game::split_prize;
+
+/// Outcome picked on one leg, the game comes from the remaining accounts.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ParlaySelection {
+    pub direction: u8,
+    pub odds: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(identifier: [u8; 32], selections: Vec<ParlaySelection>)]
+pub struct BetParlay<'info> {
+    pub player: Signer<'info>,
+    #[account(
+        mut,
+        constraint = club.has_permission(&admin, operator.key, Permission::AcceptBet)
+            @ SportsError::MissingAcceptBetPermission,
+    )]
+    pub operator: Signer<'info>,
+    // program accounts
+    pub admin: Box<Account<'info, Admin>>,
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = token_mint,
+        constraint = !club.is_paused(&admin) @ SportsError::Paused,
+    )]
+    pub club: Box<Account<'info, Club>>,
+    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
+    pub club_authority: SystemAccount<'info>,
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + Parlay::size(selections.len()),
+        seeds = [
+            b"parlay",
+            club.key().as_ref(),
+            player.key().as_ref(),
+            &identifier,
+        ],
+        bump,
+    )]
+    pub parlay: Box<Account<'info, Parlay>>,
+    // token accounts
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub player_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = club_authority,
+    )]
+    pub supply_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    // system program
+    pub system_program: Program<'info, System>,
+    // remaining accounts: one game per selection
+}
+
+#[event]
+pub struct BetParlayEvent {
+    pub club: Pubkey,
+    pub parlay: Pubkey,
+    pub player: Pubkey,
+    pub legs: u8,
+    pub stake: u64,
+    pub lock: u64,
+}
+
+pub(crate) fn _bet_parlay<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, BetParlay<'info>>,
+    identifier: [u8; 32],
+    selections: Vec<ParlaySelection>,
+    stake: u64,
+) -> Result<()> {
+    require!(
+        (MIN_PARLAY_LEGS..=MAX_PARLAY_LEGS).contains(&selections.len()),
+        SportsError::InvalidParlayLegs,
+    );
+    require_eq!(
+        ctx.remaining_accounts.len(),
+        selections.len(),
+        SportsError::InvalidRemainingAccounts,
+    );
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.player_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.supply_token_account.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        },
+    );
+    transfer_checked(cpi_ctx, stake, ctx.accounts.token_mint.decimals)?;
+
+    let lock = Parlay::payout(stake, selections.iter().map(|selection| selection.odds))?;
+
+    // update every leg game
+    let club = ctx.accounts.club.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    let mut legs = Vec::with_capacity(selections.len());
+    for (info, selection) in ctx.remaining_accounts.iter().zip(&selections) {
+        require!(info.is_writable, SportsError::InvalidRemainingAccounts);
+        require!(
+            legs.iter().all(|leg: &ParlayLeg| leg.game != info.key()),
+            SportsError::DuplicateParlayLeg,
+        );
+        let mut game = Account::<Game>::try_from(info)?;
+        require_keys_eq!(game.club, club);
+
+        ctx.accounts.club.bet_parlay_leg(
+            &mut game,
+            selection.direction,
+            selection.odds,
+            lock,
+            timestamp,
+        )?;
+        game.exit(&crate::ID)?;
+
+        legs.push(ParlayLeg {
+            game: info.key(),
+            direction: selection.direction,
+            odds: selection.odds,
+        });
+    }
+    // update club
+    ctx.accounts.club.bet_parlay(stake, lock)?;
+    // initialize parlay
+    ctx.accounts.parlay.club = club;
+    ctx.accounts.parlay.player = ctx.accounts.player.key();
+    ctx.accounts.parlay.payer = ctx.accounts.operator.key();
+    ctx.accounts.parlay.identifier = identifier;
+    ctx.accounts.parlay.stake = stake;
+    ctx.accounts.parlay.lock = lock;
+    ctx.accounts.parlay.legs = legs;
+
+    emit!(BetParlayEvent {
+        club,
+        parlay: ctx.accounts.parlay.key(),
+        player: ctx.accounts.player.key(),
+        legs: selections.len() as u8,
+        stake,
+        lock,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SettleParlay<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub player: SystemAccount<'info>,
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+    pub fee_receiver: SystemAccount<'info>,
+    // program accounts
+    #[account(has_one = fee_receiver)]
+    pub admin: Box<Account<'info, Admin>>,
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = token_mint,
+        constraint = !club.is_settle_paused(&admin) @ SportsError::Paused,
+    )]
+    pub club: Box<Account<'info, Club>>,
+    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
+    pub club_authority: SystemAccount<'info>,
+    #[account(mut, close = payer, has_one = club, has_one = player, has_one = payer)]
+    pub parlay: Box<Account<'info, Parlay>>,
+    // token accounts
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = club_authority,
+    )]
+    pub supply_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = token_mint,
+        associated_token::authority = fee_receiver,
+    )]
+    pub fee_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    // system program
+    pub system_program: Program<'info, System>,
+    // remaining accounts: the leg games in parlay order
+}
+
+#[event]
+pub struct SettleParlayEvent {
+    pub club: Pubkey,
+    pub parlay: Pubkey,
+    pub player: Pubkey,
+    pub win: bool,
+    pub prize: u64,
+    pub fee: u64,
+}
+
+pub(crate) fn _settle_parlay<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SettleParlay<'info>>,
+) -> Result<()> {
+    let parlay = &ctx.accounts.parlay;
+    require_eq!(
+        ctx.remaining_accounts.len(),
+        parlay.legs.len(),
+        SportsError::InvalidRemainingAccounts,
+    );
+
+    let mut games = Vec::with_capacity(parlay.legs.len());
+    let mut results = Vec::with_capacity(parlay.legs.len());
+    for (info, leg) in ctx.remaining_accounts.iter().zip(&parlay.legs) {
+        require_keys_eq!(info.key(), leg.game, SportsError::InvalidRemainingAccounts);
+        require!(info.is_writable, SportsError::InvalidRemainingAccounts);
+        let game = Account::<Game>::try_from(info)?;
+        results.push(game.settlement_of(leg.direction));
+        games.push(game);
+    }
+    // a lost leg settles the parlay at once, otherwise every leg must be resolved
+    let prize = parlay.resolve(&results)?.ok_or(SportsError::ParlayNotResolved)?;
+
+    // release every leg game and the club
+    for (game, leg) in games.iter_mut().zip(&parlay.legs) {
+        game.release_parlay(leg.direction, parlay.lock);
+        game.exit(&crate::ID)?;
+    }
+    let (stake, lock) = (parlay.stake, parlay.lock);
+    ctx.accounts.club.settle_parlay(stake, lock, prize);
+
+    // only the net winnings of a won parlay are charged
+    let win = Parlay::is_win(&results);
+    let fee_point = if win {
+        ctx.accounts.admin.settle_fee_point(&ctx.accounts.club)
+    } else {
+        0
+    };
+    let (_, fee) = split_prize(prize.saturating_sub(stake), fee_point);
+    if prize > 0 {
+        let club = ctx.accounts.club.key();
+        let bumps = [ctx.bumps.club_authority];
+        let signer_seeds = &[
+            &[
+                b"authority".as_slice(),
+                club.as_ref(),
+                &bumps,
+            ][..],
+        ];
+
+        if fee > 0 {
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.supply_token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.fee_token_account.to_account_info(),
+                    authority: ctx.accounts.club_authority.to_account_info(),
+                },
+                signer_seeds,
+            );
+            transfer_checked(cpi_ctx, fee, ctx.accounts.token_mint.decimals)?;
+        }
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.supply_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.player_token_account.to_account_info(),
+                authority: ctx.accounts.club_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, prize - fee, ctx.accounts.token_mint.decimals)?;
+    }
+
+    emit!(SettleParlayEvent {
+        club: ctx.accounts.club.key(),
+        parlay: ctx.accounts.parlay.key(),
+        player: ctx.accounts.player.key(),
+        win,
+        prize,
+        fee,
+    });
+
+    Ok(())
+}
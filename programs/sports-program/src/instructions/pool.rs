@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked},
+};
+
+use crate::{
+    state::{Admin, Club, Game, GameMetadata, GameMode, Credential, Market, Position},
+    error::SportsError,
+};
+use super::game::{start_game, BetEvent, StartGame};
+
+/// Starts a pari-mutuel game: stakes are pooled per outcome and the winners
+/// split the whole pool, so the club backs nothing.
+pub(crate) fn _start_pool_game(
+    ctx: Context<StartGame>,
+    identifier: [u8; 32],
+    market: Market,
+    metadata: Option<GameMetadata>,
+    outcomes: u8,
+    start_time: i64,
+    cutoff_time: i64,
+) -> Result<()> {
+    let game = Game::new(
+        ctx.accounts.club.key(),
+        identifier,
+        market,
+        metadata,
+        outcomes,
+        start_time,
+        cutoff_time,
+    )?;
+    start_game(ctx, game, GameMode::Pool)
+}
+
+#[derive(Accounts)]
+#[instruction(identifier: [u8; 32])]
+pub struct BetPool<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    // program accounts
+    pub admin: Box<Account<'info, Admin>>,
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = token_mint,
+        constraint = !club.is_paused(&admin) @ SportsError::Paused,
+    )]
+    pub club: Box<Account<'info, Club>>,
+    #[account(seeds = [b"authority", club.key().as_ref()], bump)]
+    pub club_authority: SystemAccount<'info>,
+    #[account(
+        mut,
+        has_one = club,
+        constraint = game.identifier == identifier @ SportsError::InvalidIdentifier,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + Position::SIZE,
+        seeds = [
+            b"position",
+            game.key().as_ref(),
+            player.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        init,
+        payer = player,
+        space = 8 + Credential::SIZE,
+        seeds = [
+            b"credential",
+            club.key().as_ref(),
+            player.key().as_ref(),
+            &identifier,
+            &position.bets.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub credential: Box<Account<'info, Credential>>,
+    // token accounts
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub player_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = club_authority,
+    )]
+    pub supply_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    // system program
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn _bet_pool(
+    ctx: Context<BetPool>,
+    identifier: [u8; 32],
+    direction: u8,
+    stake: u64,
+) -> Result<()> {
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.player_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.supply_token_account.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        },
+    );
+    transfer_checked(cpi_ctx, stake, ctx.accounts.token_mint.decimals)?;
+
+    // update club, game and position
+    let index = ctx.accounts.position.bets;
+    ctx.accounts.position.open(
+        ctx.accounts.game.key(),
+        ctx.accounts.player.key(),
+        ctx.accounts.player.key(),
+    );
+    ctx.accounts.club.bet_pool(
+        &mut ctx.accounts.game,
+        &mut ctx.accounts.position,
+        direction,
+        stake,
+        Clock::get()?.unix_timestamp,
+    )?;
+    // initialize credential, the payout is only known once the pool closes
+    ctx.accounts.credential.club = ctx.accounts.club.key();
+    ctx.accounts.credential.player = ctx.accounts.player.key();
+    ctx.accounts.credential.payer = ctx.accounts.player.key();
+    ctx.accounts.credential.index = index;
+    ctx.accounts.credential.identifier = identifier;
+    ctx.accounts.credential.direction = direction;
+    ctx.accounts.credential.stake = stake;
+    ctx.accounts.credential.lock = 0;
+
+    emit!(BetEvent {
+        club: ctx.accounts.club.key(),
+        credential: ctx.accounts.credential.key(),
+        player: ctx.accounts.player.key(),
+        direction,
+        stake,
+        lock: 0,
+    });
+
+    Ok(())
+}
@@ -1,10 +1,12 @@
 mod state;
 mod error;
 mod instructions;
+mod utils;
 
 use anchor_lang::prelude::*;
 
 use instructions::*;
+use state::{GameMetadata, Market, Settlement, Side};
 
 declare_id!("4BGS57PnHpNr3Sm9yAfVyKrSPweTC8TScKd8QzoLg6qa");
 
@@ -20,13 +22,45 @@ pub mod sports_program {
         _change_fee_receiver(ctx)
     }
     
-    pub fn assign_operator(ctx: Context<AssignOperator>) -> Result<()> {
-        _assign_operator(ctx)
+    pub fn propose_owner(ctx: Context<ProposeOwner>) -> Result<()> {
+        _propose_owner(ctx)
+    }
+
+    pub fn accept_owner(ctx: Context<AcceptOwner>) -> Result<()> {
+        _accept_owner(ctx)
+    }
+
+    pub fn cancel_owner_proposal(ctx: Context<CancelOwnerProposal>) -> Result<()> {
+        _cancel_owner_proposal(ctx)
+    }
+    
+    pub fn assign_operator(ctx: Context<AssignOperator>, permissions: u8) -> Result<()> {
+        _assign_operator(ctx, permissions)
     }
     
     pub fn remove_operator(ctx: Context<RemoveOperator>) -> Result<()> {
         _remove_operator(ctx)
     }
+
+    pub fn set_fees(
+        ctx: Context<SetFees>,
+        club_creation_fee: u64,
+        settle_fee_point: u16,
+    ) -> Result<()> {
+        _set_fees(ctx, club_creation_fee, settle_fee_point)
+    }
+
+    pub fn set_club_fee(ctx: Context<SetClubFee>, settle_fee_point: Option<u16>) -> Result<()> {
+        _set_club_fee(ctx, settle_fee_point)
+    }
+    
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Option<Pubkey>) -> Result<()> {
+        _set_guardian(ctx, guardian)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        _set_paused(ctx, paused)
+    }
     
     pub fn init_club(ctx: Context<InitClub>, identifier: [u8; 32]) -> Result<()> {
         _init_club(ctx, identifier)
@@ -44,14 +78,95 @@ pub mod sports_program {
         _withdraw(ctx, amount)
     }
 
-    pub fn start_game(ctx: Context<StartGame>, identifier: [u8; 32]) -> Result<()> {
-        _start_game(ctx, identifier)
+    pub fn assign_club_operator(ctx: Context<AssignClubOperator>, permissions: u8) -> Result<()> {
+        _assign_club_operator(ctx, permissions)
+    }
+
+    pub fn remove_club_operator(ctx: Context<RemoveClubOperator>) -> Result<()> {
+        _remove_club_operator(ctx)
+    }
+
+    pub fn set_accept_admin_operators(
+        ctx: Context<SetAcceptAdminOperators>,
+        accept: bool,
+    ) -> Result<()> {
+        _set_accept_admin_operators(ctx, accept)
+    }
+
+    pub fn set_club_paused(ctx: Context<SetClubPaused>, paused: bool) -> Result<()> {
+        _set_club_paused(ctx, paused)
+    }
+
+    pub fn set_bet_limits(
+        ctx: Context<SetBetLimits>,
+        min_odds: u64,
+        max_odds: u64,
+        min_stake: u64,
+        max_stake: u64,
+    ) -> Result<()> {
+        _set_bet_limits(ctx, min_odds, max_odds, min_stake, max_stake)
+    }
+
+    pub fn set_liability_caps(
+        ctx: Context<SetLiabilityCaps>,
+        max_game_liability: u64,
+        max_outcome_liability: u64,
+        max_player_liability: u64,
+    ) -> Result<()> {
+        _set_liability_caps(ctx, max_game_liability, max_outcome_liability, max_player_liability)
+    }
+
+    pub fn set_pricing_key(ctx: Context<SetPricingKey>, pricing_key: Option<Pubkey>) -> Result<()> {
+        _set_pricing_key(ctx, pricing_key)
+    }
+
+    pub fn start_game(
+        ctx: Context<StartGame>,
+        identifier: [u8; 32],
+        market: Market,
+        metadata: Option<GameMetadata>,
+        outcomes: u8,
+        start_time: i64,
+        cutoff_time: i64,
+    ) -> Result<()> {
+        _start_game(ctx, identifier, market, metadata, outcomes, start_time, cutoff_time)
+    }
+
+    pub fn start_pool_game(
+        ctx: Context<StartGame>,
+        identifier: [u8; 32],
+        market: Market,
+        metadata: Option<GameMetadata>,
+        outcomes: u8,
+        start_time: i64,
+        cutoff_time: i64,
+    ) -> Result<()> {
+        _start_pool_game(ctx, identifier, market, metadata, outcomes, start_time, cutoff_time)
+    }
+
+    pub fn set_game_metadata(
+        ctx: Context<SetGameMetadata>,
+        metadata: Option<GameMetadata>,
+    ) -> Result<()> {
+        _set_game_metadata(ctx, metadata)
     }
     
     pub fn close_game(ctx: Context<CloseGame>, cancel: bool) -> Result<()> {
         _close_game(ctx, cancel)
     }
 
+    pub fn suspend_game(ctx: Context<SuspendGame>) -> Result<()> {
+        _suspend_game(ctx)
+    }
+
+    pub fn resume_game(ctx: Context<ResumeGame>) -> Result<()> {
+        _resume_game(ctx)
+    }
+
+    pub fn cancel_game(ctx: Context<CancelGame>) -> Result<()> {
+        _cancel_game(ctx)
+    }
+
     pub fn bet(
         ctx: Context<Bet>,
         identifier: [u8; 32],
@@ -62,11 +177,103 @@ pub mod sports_program {
         _bet(ctx, identifier, direction, stake, lock)
     }
     
+    pub fn bet_pool(
+        ctx: Context<BetPool>,
+        identifier: [u8; 32],
+        direction: u8,
+        stake: u64,
+    ) -> Result<()> {
+        _bet_pool(ctx, identifier, direction, stake)
+    }
+
+    pub fn bet_with_intent(ctx: Context<BetWithIntent>, intent: BetIntent, lock: u64) -> Result<()> {
+        _bet_with_intent(ctx, intent, lock)
+    }
+    
+    pub fn bet_with_quote(
+        ctx: Context<BetWithQuote>,
+        quote: BetQuote,
+        stake: u64,
+        min_lock: u64,
+    ) -> Result<()> {
+        _bet_with_quote(ctx, quote, stake, min_lock)
+    }
+
+    pub fn close_quote_fill(ctx: Context<CloseQuoteFill>) -> Result<()> {
+        _close_quote_fill(ctx)
+    }
+    
     pub fn close_bet(ctx: Context<CloseBet>) -> Result<()> {
         _close_bet(ctx)
     }
+
+    pub fn cash_out(ctx: Context<CashOut>, stake: u64, amount: u64) -> Result<()> {
+        _cash_out(ctx, stake, amount)
+    }
+
+    pub fn void_bets<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, VoidBets<'info>>,
+    ) -> Result<()> {
+        _void_bets(ctx)
+    }
     
-    pub fn settle(ctx: Context<Settle>, direction: u8) -> Result<()> {
-        _settle(ctx, direction)
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        _close_position(ctx)
+    }
+
+    pub fn post_result(ctx: Context<PostResult>, direction: u8) -> Result<()> {
+        _post_result(ctx, direction)
+    }
+
+    pub fn post_settlements(ctx: Context<PostResult>, settlements: Vec<Settlement>) -> Result<()> {
+        _post_settlements(ctx, settlements)
+    }
+
+    pub fn settle(ctx: Context<Settle>) -> Result<()> {
+        _settle(ctx)
+    }
+
+    pub fn settle_batch<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SettleBatch<'info>>,
+    ) -> Result<()> {
+        _settle_batch(ctx)
+    }
+
+    pub fn post_offer(
+        ctx: Context<PostOffer>,
+        identifier: [u8; 32],
+        side: Side,
+        direction: u8,
+        odds: u64,
+        stake: u64,
+    ) -> Result<()> {
+        _post_offer(ctx, identifier, side, direction, odds, stake)
+    }
+
+    pub fn match_offer(ctx: Context<MatchOffer>, stake: u64) -> Result<()> {
+        _match_offer(ctx, stake)
+    }
+
+    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+        _cancel_offer(ctx)
+    }
+
+    pub fn settle_matched(ctx: Context<SettleMatched>) -> Result<()> {
+        _settle_matched(ctx)
+    }
+
+    pub fn bet_parlay<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, BetParlay<'info>>,
+        identifier: [u8; 32],
+        selections: Vec<ParlaySelection>,
+        stake: u64,
+    ) -> Result<()> {
+        _bet_parlay(ctx, identifier, selections, stake)
+    }
+
+    pub fn settle_parlay<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SettleParlay<'info>>,
+    ) -> Result<()> {
+        _settle_parlay(ctx)
     }
 }
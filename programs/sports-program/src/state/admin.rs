@@ -1,37 +1,94 @@
 use anchor_lang::prelude::*;
 
-const MAX_OPERATORS: usize = 4;
+use crate::{
+    state::{Club, Operator, Operators},
+    error::SportsError,
+};
+
+pub const DEFAULT_CLUB_CREATION_FEE: u64 = 5_000_000;
+pub const DEFAULT_SETTLE_FEE_POINT: u16 = 50;
+pub const MAX_CLUB_CREATION_FEE: u64 = 1_000_000_000;
+pub const MAX_SETTLE_FEE_POINT: u16 = 1000;
 
 #[account]
 pub struct Admin {
     pub owner: Pubkey,
+    pub pending_owner: Option<Pubkey>,
+    // may pause the protocol alongside the owner
+    pub guardian: Option<Pubkey>,
+    pub paused: bool,
     pub fee_receiver: Pubkey,
-    pub operators: Vec<Pubkey>,
+    pub club_creation_fee: u64,
+    pub settle_fee_point: u16,
+    pub operators: Vec<Operator>,
 }
 
 impl Admin {
-    pub const SIZE: usize = 32 + 32 + 4 + 32 * MAX_OPERATORS;
+    pub(crate) fn size(operators: usize) -> usize {
+        32 + 33 + 33 + 1 + 32 + 8 + 2 + 4 + Operator::SIZE * operators
+    }
+
+    /// Account size once `operator` is assigned, used to grow the account.
+    pub(crate) fn size_with_operator(&self, operator: &Pubkey) -> usize {
+        Self::size(self.operators.len_with(operator))
+    }
+
+    /// Account size once `operator` is removed, used to shrink the account.
+    pub(crate) fn size_without_operator(&self, operator: &Pubkey) -> usize {
+        Self::size(self.operators.len_without(operator))
+    }
 
     pub(crate) fn new(owner: Pubkey, fee_receiver: Pubkey) -> Self {
         Self {
             owner,
+            pending_owner: None,
+            guardian: None,
+            paused: false,
             fee_receiver,
+            club_creation_fee: DEFAULT_CLUB_CREATION_FEE,
+            settle_fee_point: DEFAULT_SETTLE_FEE_POINT,
             operators: Vec::new(),
         }
     }
 
-    pub(crate) fn is_operator(&self, operator: &Pubkey) -> bool {
-        self.operators.iter().any(|op| op == operator)
+    pub(crate) fn propose_owner(&mut self, new_owner: Pubkey) {
+        self.pending_owner = Some(new_owner);
     }
-    
-    pub(crate) fn assign_operator(&mut self, operator: Pubkey) {
-        if self.is_operator(&operator) {
-            return;
-        }
-        self.operators.push(operator);
+
+    pub(crate) fn accept_owner(&mut self, new_owner: Pubkey) -> Result<()> {
+        require!(self.pending_owner == Some(new_owner), SportsError::InvalidPendingOwner);
+        self.owner = new_owner;
+        self.pending_owner = None;
+
+        Ok(())
+    }
+
+    pub(crate) fn cancel_owner_proposal(&mut self) -> Result<Pubkey> {
+        self.pending_owner.take().ok_or_else(|| SportsError::InvalidPendingOwner.into())
+    }
+
+    pub(crate) fn can_pause(&self, authority: &Pubkey) -> bool {
+        &self.owner == authority || self.guardian.as_ref() == Some(authority)
+    }
+
+    pub(crate) fn set_fees(&mut self, club_creation_fee: u64, settle_fee_point: u16) -> Result<()> {
+        require_gte!(MAX_CLUB_CREATION_FEE, club_creation_fee, SportsError::InvalidFee);
+        require_gte!(MAX_SETTLE_FEE_POINT, settle_fee_point, SportsError::InvalidFee);
+        self.club_creation_fee = club_creation_fee;
+        self.settle_fee_point = settle_fee_point;
+
+        Ok(())
+    }
+
+    pub(crate) fn settle_fee_point(&self, club: &Club) -> u16 {
+        club.settle_fee_point.unwrap_or(self.settle_fee_point)
+    }
+
+    pub(crate) fn assign_operator(&mut self, operator: Pubkey, permissions: u8) -> Result<()> {
+        self.operators.assign_operator(operator, permissions)
     }
     
     pub(crate) fn remove_operator(&mut self, operator: &Pubkey) {
-        self.operators.retain(|op| op != operator);
+        self.operators.remove_operator(operator);
     }
 }
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{Settlement, ODDS_DIVISOR},
+    error::SportsError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    // backs the outcome to happen
+    Back,
+    // lays the outcome, paying the backer if it happens
+    Lay,
+}
+
+/// Liability of the layer against `stake` backed at `odds`.
+fn liability(stake: u64, odds: u64) -> u64 {
+    (stake as u128 * (odds - ODDS_DIVISOR) as u128 / ODDS_DIVISOR as u128) as u64
+}
+
+/// An unmatched back or lay offer on one outcome of a game.
+#[account]
+pub struct Offer {
+    pub club: Pubkey,
+    pub game: Pubkey,
+    pub maker: Pubkey,
+    pub identifier: [u8; 32],
+
+    pub side: Side,
+    pub direction: u8,
+    pub odds: u64,
+    // backer stake still open for matching
+    pub remaining: u64,
+    // maker tokens escrowed for the remaining stake
+    pub escrow: u64,
+    // number of times the offer was matched
+    pub matches: u32,
+}
+
+impl Offer {
+    pub const SIZE: usize = 32 + 32 + 32 + 32 + 1 + 1 + 8 + 8 + 8 + 4;
+
+    /// Tokens the maker escrows to offer `stake` of backer stake.
+    pub(crate) fn escrow_for(side: Side, stake: u64, odds: u64) -> Result<u64> {
+        require_gt!(stake, 0, SportsError::InvalidStakeAmount);
+        require_gt!(odds, ODDS_DIVISOR, SportsError::OddsTooLow);
+        Ok(match side {
+            Side::Back => stake,
+            Side::Lay => liability(stake, odds),
+        })
+    }
+
+    /// Matches `stake` of backer stake, returning the backer stake and the
+    /// layer liability of the matched bet.
+    pub(crate) fn fill(&mut self, stake: u64) -> Result<(u64, u64)> {
+        require!(0 < stake && stake <= self.remaining, SportsError::InvalidOfferAmount);
+        let matched_liability = match self.side {
+            Side::Back => liability(stake, self.odds),
+            // the last fill takes whatever rounding is left in escrow
+            Side::Lay if stake == self.remaining => self.escrow,
+            Side::Lay => liability(stake, self.odds),
+        };
+        self.escrow -= match self.side {
+            Side::Back => stake,
+            Side::Lay => matched_liability,
+        };
+        self.remaining -= stake;
+        self.matches += 1;
+
+        Ok((stake, matched_liability))
+    }
+
+    /// Tokens the taker escrows for a fill returned by `fill`.
+    pub(crate) fn taker_escrow(&self, stake: u64, liability: u64) -> u64 {
+        match self.side {
+            Side::Back => liability,
+            Side::Lay => stake,
+        }
+    }
+}
+
+/// A back stake matched against a lay liability, both held in escrow.
+#[account]
+pub struct MatchedBet {
+    pub club: Pubkey,
+    pub game: Pubkey,
+    pub offer: Pubkey,
+    pub backer: Pubkey,
+    pub layer: Pubkey,
+    // receives the rent back once the matched bet is closed
+    pub payer: Pubkey,
+
+    pub direction: u8,
+    pub stake: u64,
+    pub liability: u64,
+}
+
+impl MatchedBet {
+    pub const SIZE: usize = 32 * 6 + 1 + 8 + 8;
+
+    /// Splits the escrow between backer and layer under `settlement`.
+    pub(crate) fn payout(&self, settlement: Settlement) -> (u64, u64) {
+        let pot = self.stake + self.liability;
+        let (prize, refund) = settlement.payout(self.stake, pot);
+        (prize + refund, pot - prize - refund)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::fixtures::{matched_bet, offer};
+
+    #[test]
+    fn last_lay_fill_takes_escrow_rounding() {
+        // 3 at 1.5 is a liability of 1.5, escrowed as 1
+        let mut offer = offer(Side::Lay, 15000, 3);
+        assert_eq!(offer.escrow, 1);
+
+        assert_eq!(offer.fill(1).unwrap(), (1, 0));
+        assert_eq!(offer.fill(1).unwrap(), (1, 0));
+        assert_eq!(offer.fill(1).unwrap(), (1, 1));
+        assert_eq!((offer.remaining, offer.escrow, offer.matches), (0, 0, 3));
+        assert!(offer.fill(1).is_err());
+    }
+
+    #[test]
+    fn back_fill_escrows_stake_against_taker_liability() {
+        let mut offer = offer(Side::Back, 25000, 10);
+        let (stake, liability) = offer.fill(4).unwrap();
+
+        assert_eq!((stake, liability), (4, 6));
+        assert_eq!(offer.taker_escrow(stake, liability), 6);
+        assert_eq!((offer.remaining, offer.escrow), (6, 6));
+    }
+
+    #[test]
+    fn matched_payout_splits_the_pot() {
+        let matched = matched_bet(5, 4);
+
+        assert_eq!(matched.payout(Settlement::Win), (9, 0));
+        assert_eq!(matched.payout(Settlement::Lose), (0, 9));
+        assert_eq!(matched.payout(Settlement::Push), (5, 4));
+        assert_eq!(matched.payout(Settlement::HalfWin), (7, 2));
+        assert_eq!(matched.payout(Settlement::HalfLose), (2, 7));
+    }
+}
@@ -0,0 +1,127 @@
+//! Accounts in their freshly created state, shared by the state unit tests.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{
+        Club, Credential, Game, Market, MarketType, MatchedBet, Offer, Parlay, ParlayLeg,
+        Position, Side, DEFAULT_MAX_ODDS, DEFAULT_MIN_ODDS,
+    },
+    error::SportsError,
+};
+
+/// A club with the defaults of `create_club` and `liquidity` deposited.
+pub(crate) fn club(liquidity: u64) -> Club {
+    Club {
+        initialized: true,
+        admin: Pubkey::default(),
+        owner: Pubkey::default(),
+        token_mint: Pubkey::default(),
+        identifier: [0; 32],
+        staking: 0,
+        liquidity,
+        locking: 0,
+        parlay_locking: 0,
+        settle_fee_point: None,
+        min_odds: DEFAULT_MIN_ODDS,
+        max_odds: DEFAULT_MAX_ODDS,
+        min_stake: 1,
+        max_stake: u64::MAX,
+        max_game_liability: u64::MAX,
+        max_outcome_liability: u64::MAX,
+        max_player_liability: u64::MAX,
+        paused: false,
+        guardian_paused: false,
+        pricing_key: None,
+        accept_admin_operators: true,
+        operators: Vec::new(),
+    }
+}
+
+/// An open moneyline game taking bets between timestamps 0 and 100.
+pub(crate) fn game(outcomes: u8) -> Game {
+    let market = Market {
+        fixture: [0; 32],
+        market_type: MarketType::Moneyline,
+        line: 0,
+    };
+    Game::new(Pubkey::default(), [0; 32], market, None, outcomes, 0, 100).unwrap()
+}
+
+pub(crate) fn position() -> Position {
+    Position {
+        game: Pubkey::default(),
+        player: Pubkey::default(),
+        payer: Pubkey::default(),
+        locking: 0,
+        bets: 0,
+        open_bets: 0,
+    }
+}
+
+pub(crate) fn credential(direction: u8, stake: u64, lock: u64) -> Credential {
+    Credential {
+        club: Pubkey::default(),
+        player: Pubkey::default(),
+        payer: Pubkey::default(),
+        identifier: [0; 32],
+        index: 0,
+        direction,
+        stake,
+        lock,
+    }
+}
+
+pub(crate) fn offer(side: Side, odds: u64, stake: u64) -> Offer {
+    Offer {
+        club: Pubkey::default(),
+        game: Pubkey::default(),
+        maker: Pubkey::default(),
+        identifier: [0; 32],
+        side,
+        direction: 0,
+        odds,
+        remaining: stake,
+        escrow: Offer::escrow_for(side, stake, odds).unwrap(),
+        matches: 0,
+    }
+}
+
+pub(crate) fn matched_bet(stake: u64, liability: u64) -> MatchedBet {
+    MatchedBet {
+        club: Pubkey::default(),
+        game: Pubkey::default(),
+        offer: Pubkey::default(),
+        backer: Pubkey::default(),
+        layer: Pubkey::default(),
+        payer: Pubkey::default(),
+        direction: 0,
+        stake,
+        liability,
+    }
+}
+
+/// A parlay with one leg per `odds`, locking the compounded payout.
+pub(crate) fn parlay(stake: u64, odds: &[u64]) -> Parlay {
+    let legs = odds
+        .iter()
+        .map(|odds| ParlayLeg { game: Pubkey::default(), direction: 0, odds: *odds })
+        .collect::<Vec<_>>();
+    Parlay {
+        club: Pubkey::default(),
+        player: Pubkey::default(),
+        payer: Pubkey::default(),
+        identifier: [0; 32],
+        stake,
+        lock: Parlay::payout(stake, odds.iter().copied()).unwrap(),
+        legs,
+    }
+}
+
+pub(crate) fn assert_error<T>(result: Result<T>, error: SportsError) {
+    match result {
+        Err(Error::AnchorError(err)) => assert_eq!(err.error_code_number, u32::from(error)),
+        Err(err) => panic!("expected {error}, got {err}"),
+        Ok(_) => panic!("expected {error}"),
+    }
+}
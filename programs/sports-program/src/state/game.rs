@@ -1,6 +1,152 @@
 use anchor_lang::prelude::*;
 
-use crate::error::SportsError;
+use crate::{
+    state::{Admin, Operator, Operators, Permission, MAX_SETTLE_FEE_POINT},
+    error::SportsError,
+};
+
+pub const MIN_OUTCOMES: u8 = 2;
+pub const MAX_OUTCOMES: u8 = 64;
+
+// odds are lock / stake scaled by ODDS_DIVISOR
+pub const ODDS_DIVISOR: u64 = 10000;
+pub const DEFAULT_MIN_ODDS: u64 = ODDS_DIVISOR;
+pub const DEFAULT_MAX_ODDS: u64 = 100 * ODDS_DIVISOR;
+
+pub const MAX_METADATA_URI_LEN: usize = 128;
+
+// market lines are scaled by LINE_DIVISOR, e.g. -125 is a -1.25 handicap
+pub const LINE_DIVISOR: i32 = 100;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum GameStatus {
+    Scheduled,
+    Open,
+    Suspended,
+    Closed,
+    Settled,
+    Cancelled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    // the club backs a lock fixed at bet time
+    FixedOdds,
+    // stakes are pooled per outcome and split among the winners
+    Pool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MarketType {
+    Moneyline,
+    Spread,
+    Total,
+    BothTeamsToScore,
+}
+
+/// The market a game takes bets on. Several games sharing a fixture are
+/// markets of the same match, each with its own exposure and result.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Market {
+    pub fixture: [u8; 32],
+    pub market_type: MarketType,
+    // handicap for spreads and goal line for totals
+    pub line: i32,
+}
+
+impl Market {
+    pub const SIZE: usize = 32 + 1 + 4;
+
+    fn validate(&self, outcomes: u8) -> Result<()> {
+        // handicap and goal lines move in quarters
+        let quarter_line = self.line % (LINE_DIVISOR / 4) == 0;
+        let valid = match self.market_type {
+            MarketType::Moneyline => self.line == 0,
+            MarketType::Spread => outcomes == 2 && quarter_line,
+            MarketType::Total => outcomes == 2 && quarter_line && self.line >= 0,
+            MarketType::BothTeamsToScore => outcomes == 2 && self.line == 0,
+        };
+        require!(valid, SportsError::InvalidMarket);
+
+        Ok(())
+    }
+}
+
+/// What match a game is about, for indexers and UIs.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct GameMetadata {
+    pub sport: [u8; 16],
+    pub league: [u8; 32],
+    pub home_team: [u8; 8],
+    pub away_team: [u8; 8],
+    pub kickoff: i64,
+    pub uri: String,
+    // hash of the document behind the uri
+    pub uri_hash: [u8; 32],
+}
+
+impl GameMetadata {
+    pub const SIZE: usize = 16 + 32 + 8 + 8 + 8 + 4 + MAX_METADATA_URI_LEN + 32;
+}
+
+/// How the bets on one outcome are paid once the result is posted.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Settlement {
+    Lose,
+    HalfLose,
+    Push,
+    HalfWin,
+    Win,
+}
+
+impl Settlement {
+    /// Splits the payout of a bet into its net winnings, which are charged
+    /// the settle fee, and the stake returned. Every part rounds down, so
+    /// paying bets one by one never exceeds paying their sum at once.
+    pub(crate) fn payout(&self, stake: u64, lock: u64) -> (u64, u64) {
+        match self {
+            Settlement::Lose => (0, 0),
+            Settlement::HalfLose => (0, stake / 2),
+            Settlement::Push => (0, stake),
+            Settlement::HalfWin => ((lock - stake) / 2, stake),
+            Settlement::Win => (lock - stake, stake),
+        }
+    }
+
+    fn complement(&self) -> Self {
+        match self {
+            Settlement::Lose => Settlement::Win,
+            Settlement::HalfLose => Settlement::HalfWin,
+            Settlement::Push => Settlement::Push,
+            Settlement::HalfWin => Settlement::HalfLose,
+            Settlement::Win => Settlement::Lose,
+        }
+    }
+
+    /// Whether `settlements` describe a result that can actually happen: one
+    /// side against its complement on 2-way markets, otherwise a single
+    /// winner, or every outcome pushed.
+    pub(crate) fn is_coherent(settlements: &[Settlement]) -> bool {
+        if let [first, second] = settlements {
+            return *second == first.complement();
+        }
+        let winners = settlements.iter().filter(|settlement| **settlement == Settlement::Win);
+        let losers = settlements.iter().filter(|settlement| **settlement == Settlement::Lose);
+        settlements.iter().all(|settlement| *settlement == Settlement::Push)
+            || (winners.count() == 1 && losers.count() == settlements.len() - 1)
+    }
+
+    /// Odds this settlement contributes to a parlay leg bet at `odds`.
+    pub(crate) fn leg_odds(&self, odds: u64) -> u64 {
+        match self {
+            Settlement::Lose => 0,
+            Settlement::HalfLose => ODDS_DIVISOR / 2,
+            Settlement::Push => ODDS_DIVISOR,
+            Settlement::HalfWin => (odds + ODDS_DIVISOR) / 2,
+            Settlement::Win => odds,
+        }
+    }
+}
 
 #[account]
 pub struct Club {
@@ -12,19 +158,144 @@ pub struct Club {
 
     pub staking: u64,
     pub liquidity: u64,
-    pub locking_0: u64,
-    pub locking_1: u64,
-    pub locking_2: u64,
+    // sum of the worst-case loss of every open game
+    pub locking: u64,
+    // sum of the payout of every open parlay
+    pub parlay_locking: u64,
+    // overrides the admin settle fee when set
+    pub settle_fee_point: Option<u16>,
+    pub min_odds: u64,
+    pub max_odds: u64,
+    pub min_stake: u64,
+    pub max_stake: u64,
+    pub max_game_liability: u64,
+    pub max_outcome_liability: u64,
+    pub max_player_liability: u64,
+    // paused by the club owner, only stops new bets and withdrawals
+    pub paused: bool,
+    // paused by the admin owner or guardian, only they can lift it
+    pub guardian_paused: bool,
+    // signs the odds quotes players bet against
+    pub pricing_key: Option<Pubkey>,
+    // whether admin operators may act on this club
+    pub accept_admin_operators: bool,
+    pub operators: Vec<Operator>,
 }
 
 impl Club {
-    pub const SIZE: usize = std::mem::size_of::<Self>();
+    pub(crate) fn size(operators: usize) -> usize {
+        1 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 3 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 1 + 33 + 1 + 4
+            + Operator::SIZE * operators
+    }
+
+    /// Account size once `operator` is assigned, used to grow the account.
+    pub(crate) fn size_with_operator(&self, operator: &Pubkey) -> usize {
+        Self::size(self.operators.len_with(operator))
+    }
+
+    /// Account size once `operator` is removed, used to shrink the account.
+    pub(crate) fn size_without_operator(&self, operator: &Pubkey) -> usize {
+        Self::size(self.operators.len_without(operator))
+    }
+
+    pub(crate) fn is_paused(&self, admin: &Admin) -> bool {
+        self.is_settle_paused(admin) || self.paused
+    }
+
+    /// Settlement only stops for admin side pauses, so a club owner cannot
+    /// withhold payouts by pausing their own club.
+    pub(crate) fn is_settle_paused(&self, admin: &Admin) -> bool {
+        admin.paused || self.guardian_paused
+    }
+
+    pub(crate) fn set_paused(&mut self, admin: &Admin, authority: &Pubkey, paused: bool) {
+        if admin.can_pause(authority) {
+            self.guardian_paused = paused;
+        } else {
+            self.paused = paused;
+        }
+    }
+
+    pub(crate) fn has_permission(
+        &self,
+        admin: &Admin,
+        operator: &Pubkey,
+        permission: Permission,
+    ) -> bool {
+        self.operators.has_permission(operator, permission)
+            || (self.accept_admin_operators && admin.operators.has_permission(operator, permission))
+    }
+
+    pub(crate) fn assign_operator(&mut self, operator: Pubkey, permissions: u8) -> Result<()> {
+        self.operators.assign_operator(operator, permissions)
+    }
+
+    pub(crate) fn remove_operator(&mut self, operator: &Pubkey) {
+        self.operators.remove_operator(operator);
+    }
 
     pub(crate) fn can_close(&self) -> bool {
-        self.staking == 0
-            && self.locking_0 == 0
-            && self.locking_1 == 0
-            && self.locking_2 == 0
+        self.staking == 0 && self.locking == 0 && self.parlay_locking == 0
+    }
+
+    pub(crate) fn set_settle_fee_point(&mut self, settle_fee_point: Option<u16>) -> Result<()> {
+        if let Some(fee_point) = settle_fee_point {
+            require_gte!(MAX_SETTLE_FEE_POINT, fee_point, SportsError::InvalidFee);
+        }
+        self.settle_fee_point = settle_fee_point;
+
+        Ok(())
+    }
+
+    pub(crate) fn set_bet_limits(
+        &mut self,
+        min_odds: u64,
+        max_odds: u64,
+        min_stake: u64,
+        max_stake: u64,
+    ) -> Result<()> {
+        require!(
+            ODDS_DIVISOR <= min_odds
+                && min_odds <= max_odds
+                && 0 < min_stake
+                && min_stake <= max_stake,
+            SportsError::InvalidBetLimits,
+        );
+        self.min_odds = min_odds;
+        self.max_odds = max_odds;
+        self.min_stake = min_stake;
+        self.max_stake = max_stake;
+
+        Ok(())
+    }
+
+    pub(crate) fn set_liability_caps(
+        &mut self,
+        max_game_liability: u64,
+        max_outcome_liability: u64,
+        max_player_liability: u64,
+    ) {
+        self.max_game_liability = max_game_liability;
+        self.max_outcome_liability = max_outcome_liability;
+        self.max_player_liability = max_player_liability;
+    }
+
+    fn check_stake(&self, stake: u64) -> Result<()> {
+        require_gt!(stake, 0, SportsError::InvalidStakeAmount);
+        require_gte!(stake, self.min_stake, SportsError::StakeTooLow);
+        require_gte!(self.max_stake, stake, SportsError::StakeTooHigh);
+
+        Ok(())
+    }
+
+    fn check_bet_limits(&self, stake: u64, lock: u64) -> Result<()> {
+        self.check_stake(stake)?;
+        // compare lock / stake against the odds bounds without dividing
+        let scaled_lock = lock as u128 * ODDS_DIVISOR as u128;
+        require_gte!(scaled_lock, self.min_odds as u128 * stake as u128, SportsError::OddsTooLow);
+        require_gte!(self.max_odds as u128 * stake as u128, scaled_lock, SportsError::OddsTooHigh);
+
+        Ok(())
     }
 
     pub(crate) fn deposit(&mut self, amount: u64) -> Result<()> {
@@ -37,91 +308,615 @@ impl Club {
     pub(crate) fn withdraw(&mut self, amount: u64) -> Result<()> {
         require_gt!(amount, 0, SportsError::InvalidWithdrawAmount);
         self.liquidity -= amount;
+        self.check_solvency()
+    }
 
-        let insurance = self.hedged_locking().max(self.staking);
-        require_gte!(self.liquidity, insurance, SportsError::InsufficientLiquidity);
+    fn check_solvency(&self) -> Result<()> {
+        require_gte!(
+            self.liquidity,
+            self.locking + self.parlay_locking,
+            SportsError::InsufficientLiquidity,
+        );
 
         Ok(())
     }
 
-    pub(crate) fn hedged_locking(&self) -> u64 {
-        let lock_0 = self.locking_0.abs_diff(self.locking_1 + self.locking_2);
-        let lock_1 = self.locking_1.abs_diff(self.locking_0 + self.locking_2);
-        let lock_2 = self.locking_2.abs_diff(self.locking_0 + self.locking_1);
-        lock_0.max(lock_1).max(lock_2)
+    fn update_locking(&mut self, old_exposure: u64, new_exposure: u64) {
+        self.locking = self.locking - old_exposure + new_exposure;
     }
 
-    pub(crate) fn close_game(&mut self, game: &Game) {
-        self.staking -= game.staking;
+    pub(crate) fn bet(
+        &mut self,
+        game: &mut Game,
+        position: &mut Position,
+        direction: u8,
+        stake: u64,
+        lock: u64,
+        timestamp: i64,
+    ) -> Result<()> {
+        self.check_bet_limits(stake, lock)?;
+        let exposure = game.exposure();
+        game.bet(direction, stake, lock, timestamp)?;
+        self.update_locking(exposure, game.exposure());
+        position.bet(lock);
+
+        require_gte!(
+            self.max_game_liability,
+            game.exposure(),
+            SportsError::GameLiabilityExceeded,
+        );
+        require_gte!(
+            self.max_outcome_liability,
+            game.locking[direction as usize],
+            SportsError::OutcomeLiabilityExceeded,
+        );
+        require_gte!(
+            self.max_player_liability,
+            position.locking,
+            SportsError::PlayerLiabilityExceeded,
+        );
+
+        self.staking += stake;
+        self.liquidity += stake;
+        self.check_solvency()
     }
 
-    pub(crate) fn bet(&mut self, direction: u8, stake: u64, lock: u64) -> Result<()> {
-        match direction {
-            0 => self.locking_0 += lock,
-            1 => self.locking_1 += lock,
-            2 => self.locking_2 += lock,
-            _ => return Err(SportsError::InvalidDirection.into()),
-        }
+    pub(crate) fn bet_parlay_leg(
+        &self,
+        game: &mut Game,
+        direction: u8,
+        odds: u64,
+        lock: u64,
+        timestamp: i64,
+    ) -> Result<()> {
+        require_gte!(odds, self.min_odds, SportsError::OddsTooLow);
+        require_gte!(self.max_odds, odds, SportsError::OddsTooHigh);
+        game.bet_parlay(direction, lock, timestamp)?;
+
+        let liability = game.outcome_liability(direction);
+        require_gte!(
+            self.max_game_liability,
+            game.exposure().max(liability),
+            SportsError::GameLiabilityExceeded,
+        );
+        require_gte!(self.max_outcome_liability, liability, SportsError::OutcomeLiabilityExceeded);
+
+        Ok(())
+    }
+
+    pub(crate) fn bet_parlay(&mut self, stake: u64, lock: u64) -> Result<()> {
+        self.check_stake(stake)?;
+        // the combined odds are held to the same bound as a single bet
+        require_gte!(
+            self.max_odds as u128 * stake as u128,
+            lock as u128 * ODDS_DIVISOR as u128,
+            SportsError::ParlayOddsTooHigh,
+        );
+        require_gte!(self.max_player_liability, lock, SportsError::PlayerLiabilityExceeded);
+
         self.staking += stake;
         self.liquidity += stake;
-        require_gte!(self.liquidity, self.hedged_locking(), SportsError::InsufficientLiquidity);
+        self.parlay_locking += lock;
+        self.check_solvency()
+    }
+
+    pub(crate) fn settle_parlay(&mut self, stake: u64, lock: u64, payout: u64) {
+        self.staking -= stake;
+        self.parlay_locking -= lock;
+        self.liquidity -= payout;
+    }
+
+    pub(crate) fn bet_pool(
+        &mut self,
+        game: &mut Game,
+        position: &mut Position,
+        direction: u8,
+        stake: u64,
+        timestamp: i64,
+    ) -> Result<()> {
+        self.check_stake(stake)?;
+        game.bet_pool(direction, stake, timestamp)?;
+        position.bet(0);
+
+        // pooled stakes never touch the club liquidity
+        self.staking += stake;
 
         Ok(())
     }
 
-    pub(crate) fn close_bet(&mut self, credential: &Credential) -> Result<()> {
-        match credential.direction {
-            0 => self.locking_0 -= credential.lock,
-            1 => self.locking_1 -= credential.lock,
-            2 => self.locking_2 -= credential.lock,
-            _ => return Err(SportsError::InvalidDirection.into()),
-        }
+    pub(crate) fn close_bet(
+        &mut self,
+        game: &mut Game,
+        position: &mut Position,
+        credential: &Credential,
+    ) -> Result<()> {
+        let exposure = game.exposure();
+        game.close_bet(credential)?;
+        self.update_locking(exposure, game.exposure());
+        position.close_bet(credential);
+
         self.staking -= credential.stake;
-        self.liquidity -= credential.stake;
+        if !game.is_pool() {
+            self.liquidity -= credential.stake;
+        }
 
         Ok(())
     }
 
-    pub(crate) fn settle(&mut self, win: bool, credential: &Credential) -> Result<()> {
-        match credential.direction {
-            0 => self.locking_0 -= credential.lock,
-            1 => self.locking_1 -= credential.lock,
-            2 => self.locking_2 -= credential.lock,
-            _ => return Err(SportsError::InvalidDirection.into()),
-        }
-        if win {
-            self.liquidity -= credential.lock;
+    /// Holds exchange funds, which back players against each other and never
+    /// touch the club liquidity.
+    pub(crate) fn escrow(&mut self, amount: u64) {
+        self.staking += amount;
+    }
+
+    pub(crate) fn release_escrow(&mut self, amount: u64) {
+        self.staking -= amount;
+    }
+
+    /// Leaves the rounding left over in a settled pool to the club.
+    pub(crate) fn close_game(&mut self, game: &Game) {
+        if game.is_pool() {
+            self.staking -= game.staking;
+            self.liquidity += game.staking;
         }
+    }
+
+    /// Buys back `stake` of the credential for `amount`, returning the
+    /// released lock.
+    pub(crate) fn cash_out(
+        &mut self,
+        game: &mut Game,
+        position: &mut Position,
+        credential: &mut Credential,
+        stake: u64,
+        amount: u64,
+    ) -> Result<u64> {
+        let lock = credential.cash_out(stake)?;
+        require_gte!(lock, amount, SportsError::InvalidCashOutAmount);
+        let exposure = game.exposure();
+        game.cash_out(credential.direction, stake, lock)?;
+        self.update_locking(exposure, game.exposure());
+        position.cash_out(lock, credential);
+
+        self.staking -= stake;
+        self.liquidity -= amount;
+        self.check_solvency()?;
+
+        Ok(lock)
+    }
+
+    pub(crate) fn post_result(
+        &mut self,
+        game: &mut Game,
+        settlements: Vec<Settlement>,
+        operator: Pubkey,
+        timestamp: i64,
+    ) -> Result<()> {
+        let exposure = game.exposure();
+        game.post_result(settlements, operator, timestamp)?;
+        self.update_locking(exposure, game.exposure());
+        self.check_solvency()
+    }
+
+    pub(crate) fn cancel_game(&mut self, game: &mut Game) -> Result<()> {
+        let exposure = game.exposure();
+        game.cancel()?;
+        self.update_locking(exposure, game.exposure());
 
         Ok(())
     }
+
+    /// Settles the credential, returning its winning and refunded portions.
+    pub(crate) fn settle(
+        &mut self,
+        game: &mut Game,
+        position: &mut Position,
+        credential: &Credential,
+    ) -> Result<(u64, u64)> {
+        let exposure = game.exposure();
+        let (prize, refund) = game.settle(credential)?;
+        self.update_locking(exposure, game.exposure());
+        position.close_bet(credential);
+
+        if game.is_pool() {
+            self.staking -= prize + refund;
+        } else {
+            self.staking -= credential.stake;
+            self.liquidity -= prize + refund;
+        }
+
+        Ok((prize, refund))
+    }
 }
 
 #[account]
 pub struct Game {
     pub club: Pubkey,
     pub identifier: [u8; 32],
+    pub market: Market,
+    pub metadata: Option<GameMetadata>,
+    pub mode: GameMode,
+    pub status: GameStatus,
+    pub start_time: i64,
+    pub cutoff_time: i64,
+    // stakes of open bets, or what is left of the pool in pool mode
     pub staking: u64,
+    // payout locked on each outcome, or the stakes pooled on it in pool mode
+    pub locking: Vec<u64>,
+    // stakes of the fixed odds bets on each outcome
+    pub outcome_staking: Vec<u64>,
+    // open bets in pool mode
+    pub pool_bets: u32,
+    // open exchange offers and matched bets
+    pub exchange_bets: u32,
+    // matched bets ever made, used as the next matched bet index
+    pub matched_bets: u32,
+    // open parlays with a leg on this game
+    pub parlays: u32,
+    // parlay payout locked on each outcome
+    pub parlay_locking: Vec<u64>,
+
+    // outcome settled as a full win, u8::MAX if none
+    pub final_direction: u8,
+    // settlement of each outcome once the result is posted
+    pub settlements: Vec<Settlement>,
+    pub result_operator: Pubkey,
+    pub result_timestamp: i64,
 }
 
 impl Game {
-    pub const SIZE: usize = std::mem::size_of::<Self>();
+    pub(crate) fn size(outcomes: u8) -> usize {
+        32 + 32 + Market::SIZE + 1 + GameMetadata::SIZE + 1 + 1 + 8 + 8 + 8
+            + 4 + 8 * outcomes as usize + 4 + 8 * outcomes as usize
+            + 4 + 4 + 4 + 4 + 4 + 8 * outcomes as usize
+            + 1 + 4 + outcomes as usize + 32 + 8
+    }
+
+    pub(crate) fn new(
+        club: Pubkey,
+        identifier: [u8; 32],
+        market: Market,
+        metadata: Option<GameMetadata>,
+        outcomes: u8,
+        start_time: i64,
+        cutoff_time: i64,
+    ) -> Result<Self> {
+        require!(
+            (MIN_OUTCOMES..=MAX_OUTCOMES).contains(&outcomes),
+            SportsError::InvalidOutcomes,
+        );
+        market.validate(outcomes)?;
+        Self::validate_metadata(&metadata)?;
+        require_gt!(cutoff_time, start_time, SportsError::InvalidGameTime);
+        Ok(Self {
+            club,
+            identifier,
+            market,
+            metadata,
+            mode: GameMode::FixedOdds,
+            status: GameStatus::Open,
+            start_time,
+            cutoff_time,
+            staking: 0,
+            locking: vec![0; outcomes as usize],
+            outcome_staking: vec![0; outcomes as usize],
+            pool_bets: 0,
+            exchange_bets: 0,
+            matched_bets: 0,
+            parlays: 0,
+            parlay_locking: vec![0; outcomes as usize],
+            final_direction: u8::MAX,
+            settlements: Vec::new(),
+            result_operator: Pubkey::default(),
+            result_timestamp: 0,
+        })
+    }
+
+    pub(crate) fn with_mode(mut self, mode: GameMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub(crate) fn is_pool(&self) -> bool {
+        self.mode == GameMode::Pool
+    }
+
+    pub(crate) fn is_finalized(&self) -> bool {
+        self.status == GameStatus::Settled
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.status == GameStatus::Cancelled
+    }
+
+    /// Status as seen at `timestamp`: an open game has not started before
+    /// `start_time` and stops taking bets at `cutoff_time`.
+    pub(crate) fn status_at(&self, timestamp: i64) -> GameStatus {
+        match self.status {
+            GameStatus::Open | GameStatus::Suspended if timestamp >= self.cutoff_time => {
+                GameStatus::Closed
+            }
+            GameStatus::Open if timestamp < self.start_time => GameStatus::Scheduled,
+            status => status,
+        }
+    }
 
     pub(crate) fn can_close(&self, cancel: bool) -> bool {
-        if cancel {
-            // must close all bets!
+        // must close or settle all bets and parlays!
+        let no_bets = if self.is_pool() {
+            self.pool_bets == 0
+        } else {
             self.staking == 0
+        };
+        let no_bets = no_bets && self.parlays == 0 && self.exchange_bets == 0;
+        if cancel {
+            !self.is_finalized() && no_bets
         } else {
-            true
+            self.is_finalized() && no_bets
+        }
+    }
+
+    fn validate_metadata(metadata: &Option<GameMetadata>) -> Result<()> {
+        if let Some(metadata) = metadata {
+            require_gte!(
+                MAX_METADATA_URI_LEN,
+                metadata.uri.len(),
+                SportsError::InvalidMetadata,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn set_metadata(
+        &mut self,
+        metadata: Option<GameMetadata>,
+        timestamp: i64,
+    ) -> Result<()> {
+        // updatable until the game closes at cutoff
+        require!(
+            matches!(
+                self.status_at(timestamp),
+                GameStatus::Scheduled | GameStatus::Open | GameStatus::Suspended,
+            ),
+            SportsError::InvalidGameStatus,
+        );
+        Self::validate_metadata(&metadata)?;
+        self.metadata = metadata;
+
+        Ok(())
+    }
+
+    pub(crate) fn suspend(&mut self) -> Result<()> {
+        require!(self.status == GameStatus::Open, SportsError::InvalidGameStatus);
+        self.status = GameStatus::Suspended;
+
+        Ok(())
+    }
+
+    pub(crate) fn resume(&mut self) -> Result<()> {
+        require!(self.status == GameStatus::Suspended, SportsError::InvalidGameStatus);
+        self.status = GameStatus::Open;
+
+        Ok(())
+    }
+
+    pub(crate) fn cancel(&mut self) -> Result<()> {
+        require!(
+            self.status == GameStatus::Open || self.status == GameStatus::Suspended,
+            SportsError::InvalidGameStatus,
+        );
+        self.status = GameStatus::Cancelled;
+
+        Ok(())
+    }
+
+    fn locking_mut(&mut self, direction: u8) -> Result<&mut u64> {
+        self.locking
+            .get_mut(direction as usize)
+            .ok_or_else(|| SportsError::InvalidDirection.into())
+    }
+
+    /// The most the club can lose on this game: the largest payout of any
+    /// outcome, what every outcome still pays out once settled, or refunding
+    /// every stake if the game gets cancelled. Pools pay out of their own
+    /// stakes and expose nothing.
+    pub(crate) fn exposure(&self) -> u64 {
+        if self.is_pool() {
+            return 0;
+        }
+        match self.status {
+            GameStatus::Settled => self.settlements
+                .iter()
+                .zip(self.outcome_staking.iter().zip(&self.locking))
+                .map(|(settlement, (stake, lock))| {
+                    let (prize, refund) = settlement.payout(*stake, *lock);
+                    prize + refund
+                })
+                .sum(),
+            GameStatus::Cancelled => self.staking,
+            _ => self.locking
+                .iter()
+                .copied()
+                .max()
+                .unwrap_or_default()
+                .max(self.staking),
         }
     }
 
-    pub(crate) fn bet(&mut self, stake: u64) {
+    /// Settlements where `direction` wins and every other outcome loses.
+    pub(crate) fn single_winner(&self, direction: u8) -> Result<Vec<Settlement>> {
+        require_gt!(self.locking.len(), direction as usize, SportsError::InvalidDirection);
+        let mut settlements = vec![Settlement::Lose; self.locking.len()];
+        settlements[direction as usize] = Settlement::Win;
+
+        Ok(settlements)
+    }
+
+    pub(crate) fn post_result(
+        &mut self,
+        settlements: Vec<Settlement>,
+        operator: Pubkey,
+        timestamp: i64,
+    ) -> Result<()> {
+        require!(!self.is_finalized(), SportsError::GameFinalized);
+        require!(!self.is_cancelled(), SportsError::InvalidGameStatus);
+        require_eq!(settlements.len(), self.locking.len(), SportsError::InvalidSettlements);
+        if self.is_pool() {
+            // a pool is only split between winners and losers
+            require!(
+                settlements
+                    .iter()
+                    .all(|settlement| matches!(settlement, Settlement::Win | Settlement::Lose)),
+                SportsError::InvalidSettlements,
+            );
+        } else {
+            require!(Settlement::is_coherent(&settlements), SportsError::InvalidSettlements);
+        }
+        self.status = GameStatus::Settled;
+        self.final_direction = settlements
+            .iter()
+            .position(|settlement| *settlement == Settlement::Win)
+            .map_or(u8::MAX, |direction| direction as u8);
+        self.settlements = settlements;
+        self.result_operator = operator;
+        self.result_timestamp = timestamp;
+
+        Ok(())
+    }
+
+    pub(crate) fn bet(
+        &mut self,
+        direction: u8,
+        stake: u64,
+        lock: u64,
+        timestamp: i64,
+    ) -> Result<()> {
+        require!(!self.is_pool(), SportsError::InvalidGameMode);
+        require!(self.status_at(timestamp) == GameStatus::Open, SportsError::GameNotOpen);
+        *self.locking_mut(direction)? += lock;
+        self.outcome_staking[direction as usize] += stake;
+        self.staking += stake;
+
+        Ok(())
+    }
+
+    pub(crate) fn bet_pool(&mut self, direction: u8, stake: u64, timestamp: i64) -> Result<()> {
+        require!(self.is_pool(), SportsError::InvalidGameMode);
+        require!(self.status_at(timestamp) == GameStatus::Open, SportsError::GameNotOpen);
+        *self.locking_mut(direction)? += stake;
         self.staking += stake;
+        self.pool_bets += 1;
+
+        Ok(())
+    }
+
+    /// Single and parlay payout locked on `direction`.
+    pub(crate) fn outcome_liability(&self, direction: u8) -> u64 {
+        self.locking[direction as usize] + self.parlay_locking[direction as usize]
+    }
+
+    pub(crate) fn bet_parlay(&mut self, direction: u8, lock: u64, timestamp: i64) -> Result<()> {
+        require!(!self.is_pool(), SportsError::InvalidGameMode);
+        require!(self.status_at(timestamp) == GameStatus::Open, SportsError::GameNotOpen);
+        require_gt!(self.parlay_locking.len(), direction as usize, SportsError::InvalidDirection);
+        self.parlay_locking[direction as usize] += lock;
+        self.parlays += 1;
+
+        Ok(())
+    }
+
+    pub(crate) fn open_exchange_bet(&mut self, direction: u8, timestamp: i64) -> Result<()> {
+        require!(self.status_at(timestamp) == GameStatus::Open, SportsError::GameNotOpen);
+        require_gt!(self.locking.len(), direction as usize, SportsError::InvalidDirection);
+        self.exchange_bets += 1;
+
+        Ok(())
+    }
+
+    /// Opens a matched bet, which a game only closes once every exchange bet
+    /// is gone, so matched bet indexes never repeat while one is open.
+    pub(crate) fn open_matched_bet(&mut self, direction: u8, timestamp: i64) -> Result<()> {
+        self.open_exchange_bet(direction, timestamp)?;
+        self.matched_bets += 1;
+
+        Ok(())
+    }
+
+    pub(crate) fn close_exchange_bet(&mut self) {
+        self.exchange_bets -= 1;
+    }
+
+    pub(crate) fn release_parlay(&mut self, direction: u8, lock: u64) {
+        self.parlay_locking[direction as usize] -= lock;
+        self.parlays -= 1;
+    }
+
+    /// Settlement of a parlay leg or matched bet on `direction`, `None`
+    /// while unresolved.
+    pub(crate) fn settlement_of(&self, direction: u8) -> Option<Settlement> {
+        match self.status {
+            GameStatus::Settled => Some(self.settlements[direction as usize]),
+            GameStatus::Cancelled => Some(Settlement::Push),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn close_bet(&mut self, credential: &Credential) -> Result<()> {
+        if self.is_pool() {
+            *self.locking_mut(credential.direction)? -= credential.stake;
+            self.pool_bets -= 1;
+        } else {
+            *self.locking_mut(credential.direction)? -= credential.lock;
+            self.outcome_staking[credential.direction as usize] -= credential.stake;
+        }
+        self.staking -= credential.stake;
+
+        Ok(())
     }
 
-    pub(crate) fn close_bet(&mut self, stake: u64) {
+    pub(crate) fn cash_out(&mut self, direction: u8, stake: u64, lock: u64) -> Result<()> {
+        require!(!self.is_pool(), SportsError::InvalidGameMode);
+        *self.locking_mut(direction)? -= lock;
+        self.outcome_staking[direction as usize] -= stake;
         self.staking -= stake;
+
+        Ok(())
+    }
+
+    /// Winners split the whole pool pro rata to their stakes, and every
+    /// stake is refunded when nobody picked a winning outcome.
+    fn pool_payout(&self, credential: &Credential) -> (u64, u64) {
+        let total: u128 = self.locking.iter().map(|pool| *pool as u128).sum();
+        let winning: u128 = self.locking
+            .iter()
+            .zip(&self.settlements)
+            .filter(|(_, settlement)| **settlement == Settlement::Win)
+            .map(|(pool, _)| *pool as u128)
+            .sum();
+        if winning == 0 {
+            (0, credential.stake)
+        } else if self.settlements[credential.direction as usize] == Settlement::Win {
+            ((credential.stake as u128 * total / winning) as u64, 0)
+        } else {
+            (0, 0)
+        }
+    }
+
+    pub(crate) fn settle(&mut self, credential: &Credential) -> Result<(u64, u64)> {
+        if self.is_pool() {
+            // pools stay untouched so every winner gets the same share
+            let (prize, refund) = self.pool_payout(credential);
+            self.staking -= prize + refund;
+            self.pool_bets -= 1;
+
+            return Ok((prize, refund));
+        }
+        *self.locking_mut(credential.direction)? -= credential.lock;
+        self.outcome_staking[credential.direction as usize] -= credential.stake;
+        self.staking -= credential.stake;
+
+        Ok(self.settlements[credential.direction as usize].payout(credential.stake, credential.lock))
     }
 }
 
@@ -129,7 +924,10 @@ impl Game {
 pub struct Credential {
     pub club: Pubkey,
     pub player: Pubkey,
+    // receives the rent back once the credential is closed
+    pub payer: Pubkey,
     pub identifier: [u8; 32],
+    pub index: u32,
 
     pub direction: u8,
     pub stake: u64,
@@ -138,4 +936,428 @@ pub struct Credential {
 
 impl Credential {
     pub const SIZE: usize = std::mem::size_of::<Self>();
+
+    /// Shrinks stake and lock proportionally, returning the lock released.
+    pub(crate) fn cash_out(&mut self, stake: u64) -> Result<u64> {
+        require!(0 < stake && stake <= self.stake, SportsError::InvalidStakeAmount);
+        let lock = (self.lock as u128 * stake as u128 / self.stake as u128) as u64;
+        self.stake -= stake;
+        self.lock -= lock;
+
+        Ok(lock)
+    }
+}
+
+/// A player's open liability on one game.
+#[account]
+pub struct Position {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    // receives the rent back once the position is closed
+    pub payer: Pubkey,
+    pub locking: u64,
+    // number of bets placed, used as the next credential index
+    pub bets: u32,
+    // bets not yet closed, cashed out, voided or settled
+    pub open_bets: u32,
+}
+
+impl Position {
+    pub const SIZE: usize = std::mem::size_of::<Self>();
+
+    pub(crate) fn open(&mut self, game: Pubkey, player: Pubkey, payer: Pubkey) {
+        if self.game == Pubkey::default() {
+            self.game = game;
+            self.player = player;
+            self.payer = payer;
+        }
+    }
+
+    pub(crate) fn bet(&mut self, lock: u64) {
+        self.locking += lock;
+        self.bets += 1;
+        self.open_bets += 1;
+    }
+
+    pub(crate) fn close_bet(&mut self, credential: &Credential) {
+        self.locking -= credential.lock;
+        self.open_bets -= 1;
+    }
+
+    /// Releases `lock` of a credential already shrunk by the cash out.
+    pub(crate) fn cash_out(&mut self, lock: u64, credential: &Credential) {
+        self.locking -= lock;
+        if credential.stake == 0 {
+            self.open_bets -= 1;
+        }
+    }
+}
+
+#[account]
+pub struct IntentNonce {
+    pub club: Pubkey,
+    pub player: Pubkey,
+    pub nonce: u64,
+}
+
+impl IntentNonce {
+    pub const SIZE: usize = std::mem::size_of::<Self>();
+
+    pub(crate) fn consume(&mut self, nonce: u64) -> Result<()> {
+        require_gt!(nonce, self.nonce, SportsError::InvalidNonce);
+        self.nonce = nonce;
+
+        Ok(())
+    }
+}
+
+/// Stake already filled against one signed quote.
+#[account]
+pub struct QuoteFill {
+    pub club: Pubkey,
+    // receives the rent back once the quote expires
+    pub payer: Pubkey,
+    pub nonce: u64,
+    pub expiry_slot: u64,
+    pub filled: u64,
+}
+
+impl QuoteFill {
+    pub const SIZE: usize = std::mem::size_of::<Self>();
+
+    pub(crate) fn is_expired(&self, slot: u64) -> bool {
+        slot > self.expiry_slot
+    }
+
+    pub(crate) fn fill(&mut self, stake: u64, max_stake: u64) -> Result<()> {
+        self.filled += stake;
+        require_gte!(max_stake, self.filled, SportsError::StakeExceedsQuote);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{
+        fixtures::{assert_error, club, credential, game, position},
+        Parlay,
+    };
+
+    fn pool_game(outcomes: u8) -> Game {
+        game(outcomes).with_mode(GameMode::Pool)
+    }
+
+    fn settle_all(game: &mut Game, credentials: &[Credential]) -> Vec<(u64, u64)> {
+        credentials.iter().map(|credential| game.settle(credential).unwrap()).collect()
+    }
+
+    #[test]
+    fn pool_without_winners_refunds_every_stake() {
+        let mut game = pool_game(3);
+        let credentials = [credential(0, 100, 0), credential(1, 50, 0)];
+        for credential in &credentials {
+            game.bet_pool(credential.direction, credential.stake, 50).unwrap();
+        }
+        let settlements = vec![Settlement::Lose, Settlement::Lose, Settlement::Win];
+        game.post_result(settlements, Pubkey::default(), 100).unwrap();
+
+        assert_eq!(settle_all(&mut game, &credentials), vec![(0, 100), (0, 50)]);
+        assert_eq!(game.staking, 0);
+        assert_eq!(game.pool_bets, 0);
+    }
+
+    #[test]
+    fn pool_split_rounds_down_pro_rata() {
+        let mut game = pool_game(2);
+        let credentials = [credential(0, 1, 0), credential(0, 2, 0), credential(1, 8, 0)];
+        for credential in &credentials {
+            game.bet_pool(credential.direction, credential.stake, 50).unwrap();
+        }
+        let settlements = game.single_winner(0).unwrap();
+        game.post_result(settlements, Pubkey::default(), 100).unwrap();
+
+        // 11 pooled over a winning pool of 3
+        assert_eq!(settle_all(&mut game, &credentials), vec![(3, 0), (7, 0), (0, 0)]);
+        // the rounding stays behind for the club
+        assert_eq!(game.staking, 1);
+        assert_eq!(game.pool_bets, 0);
+    }
+
+    #[test]
+    fn half_results_split_odd_stakes() {
+        assert_eq!(Settlement::Win.payout(5, 9), (4, 5));
+        assert_eq!(Settlement::HalfWin.payout(5, 9), (2, 5));
+        assert_eq!(Settlement::HalfWin.payout(5, 8), (1, 5));
+        assert_eq!(Settlement::HalfLose.payout(5, 9), (0, 2));
+        assert_eq!(Settlement::Push.payout(5, 9), (0, 5));
+        // a half win at even odds only returns the stake
+        assert_eq!(Settlement::HalfWin.payout(5, 5), (0, 5));
+    }
+
+    #[test]
+    fn only_coherent_settlements_are_accepted() {
+        use Settlement::*;
+
+        assert!(Settlement::is_coherent(&[HalfWin, HalfLose]));
+        assert!(Settlement::is_coherent(&[Push, Push]));
+        assert!(Settlement::is_coherent(&[Lose, Win, Lose]));
+        assert!(Settlement::is_coherent(&[Push, Push, Push]));
+        assert!(!Settlement::is_coherent(&[Win, Win]));
+        assert!(!Settlement::is_coherent(&[HalfWin, Lose]));
+        assert!(!Settlement::is_coherent(&[Win, Win, Lose]));
+        assert!(!Settlement::is_coherent(&[HalfWin, Lose, Lose]));
+    }
+
+    #[test]
+    fn final_direction_is_only_set_by_a_full_win() {
+        let mut pushed = game(3);
+        assert_eq!(pushed.final_direction, u8::MAX);
+        pushed.post_result(vec![Settlement::Push; 3], Pubkey::default(), 100).unwrap();
+        assert_eq!(pushed.final_direction, u8::MAX);
+
+        let mut won = game(3);
+        let settlements = won.single_winner(2).unwrap();
+        won.post_result(settlements, Pubkey::default(), 100).unwrap();
+        assert_eq!(won.final_direction, 2);
+    }
+
+    #[test]
+    fn exposure_is_the_worst_outcome_of_each_game() {
+        let mut game = game(3);
+        game.bet(0, 100, 250, 50).unwrap();
+        game.bet(1, 100, 180, 50).unwrap();
+        assert_eq!(game.exposure(), 250);
+
+        // refunding every stake can cost more than any single outcome
+        let mut cancelled = game.clone();
+        cancelled.bet(2, 300, 310, 50).unwrap();
+        assert_eq!(cancelled.exposure(), 500);
+        cancelled.cancel().unwrap();
+        assert_eq!(cancelled.exposure(), 500);
+    }
+
+    #[test]
+    fn games_do_not_hedge_each_other() {
+        let mut club = club(200);
+        let (mut home, mut away) = (game(2), game(2));
+        club.bet(&mut home, &mut position(), 0, 100, 200, 50).unwrap();
+        club.bet(&mut away, &mut position(), 1, 100, 200, 50).unwrap();
+        // each game on its own needs its whole lock
+        assert_eq!((club.locking, club.liquidity), (400, 400));
+        assert_error(club.withdraw(1), SportsError::InsufficientLiquidity);
+    }
+
+    #[test]
+    fn bets_and_withdrawals_keep_the_club_solvent() {
+        let mut funded = club(100);
+        funded.bet(&mut game(2), &mut position(), 0, 100, 200, 50).unwrap();
+        assert_eq!((funded.locking, funded.liquidity), (200, 200));
+        assert_error(funded.withdraw(1), SportsError::InsufficientLiquidity);
+
+        let mut underfunded = club(99);
+        assert_error(
+            underfunded.bet(&mut game(2), &mut position(), 0, 100, 200, 50),
+            SportsError::InsufficientLiquidity,
+        );
+    }
+
+    #[test]
+    fn quote_fills_stop_at_max_stake() {
+        let mut quote_fill = QuoteFill {
+            club: Pubkey::default(),
+            payer: Pubkey::default(),
+            nonce: 1,
+            expiry_slot: 10,
+            filled: 0,
+        };
+        quote_fill.fill(60, 100).unwrap();
+        quote_fill.fill(40, 100).unwrap();
+        assert_error(quote_fill.fill(1, 100), SportsError::StakeExceedsQuote);
+
+        assert!(!quote_fill.is_expired(10));
+        assert!(quote_fill.is_expired(11));
+    }
+
+    #[test]
+    fn bets_stay_within_club_odds_and_stake_bounds() {
+        let mut club = club(u64::MAX / 2);
+        club.set_bet_limits(15000, 30000, 10, 1000).unwrap();
+        let mut bet = |stake, lock| club.bet(&mut game(2), &mut position(), 0, stake, lock, 50);
+
+        bet(10, 15).unwrap();
+        bet(1000, 3000).unwrap();
+        assert_error(bet(9, 27), SportsError::StakeTooLow);
+        assert_error(bet(1001, 2000), SportsError::StakeTooHigh);
+        assert_error(bet(100, 149), SportsError::OddsTooLow);
+        assert_error(bet(100, 301), SportsError::OddsTooHigh);
+        // a 10,000x lock is refused however much liquidity backs it
+        assert_error(bet(100, 1_000_000), SportsError::OddsTooHigh);
+    }
+
+    #[test]
+    fn bet_limits_must_be_ordered() {
+        let mut club = club(0);
+        assert_error(club.set_bet_limits(9999, 20000, 1, 10), SportsError::InvalidBetLimits);
+        assert_error(club.set_bet_limits(20000, 15000, 1, 10), SportsError::InvalidBetLimits);
+        assert_error(club.set_bet_limits(15000, 20000, 0, 10), SportsError::InvalidBetLimits);
+        assert_error(club.set_bet_limits(15000, 20000, 11, 10), SportsError::InvalidBetLimits);
+        club.set_bet_limits(ODDS_DIVISOR, ODDS_DIVISOR, 10, 10).unwrap();
+    }
+
+    #[test]
+    fn liability_caps_bound_each_outcome_and_player() {
+        let mut club = club(10_000);
+        club.set_liability_caps(1000, 600, 400);
+        let mut game = game(3);
+        let (mut sharp, mut other) = (position(), position());
+
+        club.bet(&mut game, &mut sharp, 0, 100, 400, 50).unwrap();
+        assert_error(
+            club.bet(&mut game, &mut sharp, 1, 10, 20, 50),
+            SportsError::PlayerLiabilityExceeded,
+        );
+        club.bet(&mut game, &mut other, 0, 100, 200, 50).unwrap();
+        assert_error(
+            club.bet(&mut game, &mut position(), 0, 1, 2, 50),
+            SportsError::OutcomeLiabilityExceeded,
+        );
+    }
+
+    #[test]
+    fn game_liability_cap_spans_every_outcome() {
+        let mut club = club(10_000);
+        club.set_liability_caps(300, u64::MAX, u64::MAX);
+        let mut game = game(2);
+        club.bet(&mut game, &mut position(), 0, 100, 300, 50).unwrap();
+        club.bet(&mut game, &mut position(), 1, 100, 300, 50).unwrap();
+        assert_error(
+            club.bet(&mut game, &mut position(), 1, 1, 1, 50),
+            SportsError::GameLiabilityExceeded,
+        );
+    }
+
+    #[test]
+    fn settling_and_voiding_release_the_position() {
+        let mut club = club(1000);
+        let mut game = game(2);
+        let mut position = position();
+        let (won, voided) = (credential(0, 100, 200), credential(1, 100, 150));
+        club.bet(&mut game, &mut position, 0, 100, 200, 50).unwrap();
+        club.bet(&mut game, &mut position, 1, 100, 150, 50).unwrap();
+        assert_eq!((position.locking, position.open_bets), (350, 2));
+
+        club.close_bet(&mut game, &mut position, &voided).unwrap();
+        assert_eq!((position.locking, position.open_bets), (200, 1));
+
+        let settlements = game.single_winner(0).unwrap();
+        club.post_result(&mut game, settlements, Pubkey::default(), 100).unwrap();
+        club.settle(&mut game, &mut position, &won).unwrap();
+        assert_eq!((position.locking, position.open_bets, position.bets), (0, 0, 2));
+        assert_eq!((club.locking, club.staking), (0, 0));
+    }
+
+    #[test]
+    fn parlay_combined_odds_stay_within_max_odds() {
+        let mut club = club(u64::MAX / 2);
+        club.set_bet_limits(ODDS_DIVISOR, 50 * ODDS_DIVISOR, 1, u64::MAX).unwrap();
+
+        club.bet_parlay(100, 5000).unwrap();
+        // three legs at the maximum odds compound far past it
+        let lock = Parlay::payout(100, [50 * ODDS_DIVISOR; 3].into_iter()).unwrap();
+        assert_error(club.bet_parlay(100, lock), SportsError::ParlayOddsTooHigh);
+        assert_error(club.bet_parlay(100, 5001), SportsError::ParlayOddsTooHigh);
+    }
+
+    #[test]
+    fn partial_cash_out_shrinks_stake_and_lock_proportionally() {
+        assert_error(credential(0, 30, 100).cash_out(0), SportsError::InvalidStakeAmount);
+        assert_error(credential(0, 30, 100).cash_out(31), SportsError::InvalidStakeAmount);
+
+        let mut cashed = credential(0, 30, 100);
+        assert_eq!(cashed.cash_out(10).unwrap(), 33);
+        assert_eq!((cashed.stake, cashed.lock), (20, 67));
+        // the last cash out takes the rounding left in the lock
+        assert_eq!(cashed.cash_out(20).unwrap(), 67);
+        assert_eq!((cashed.stake, cashed.lock), (0, 0));
+    }
+
+    #[test]
+    fn cash_out_releases_the_bought_back_lock() {
+        let mut club = club(1000);
+        let mut game = game(2);
+        let mut position = position();
+        let mut credential = credential(0, 100, 300);
+        club.bet(&mut game, &mut position, 0, 100, 300, 50).unwrap();
+
+        assert_error(
+            club.cash_out(&mut game, &mut position, &mut credential.clone(), 50, 151),
+            SportsError::InvalidCashOutAmount,
+        );
+        assert_eq!(club.cash_out(&mut game, &mut position, &mut credential, 50, 120).unwrap(), 150);
+        assert_eq!((game.locking[0], game.staking), (150, 50));
+        assert_eq!((position.locking, position.open_bets), (150, 1));
+        assert_eq!((club.locking, club.staking, club.liquidity), (150, 50, 980));
+
+        club.cash_out(&mut game, &mut position, &mut credential, 50, 0).unwrap();
+        assert_eq!((position.locking, position.open_bets), (0, 0));
+        assert_eq!((club.locking, club.staking, club.liquidity), (0, 0, 980));
+    }
+
+    #[test]
+    fn settled_exposure_is_what_the_result_pays_out() {
+        // an even 2-way handicap with 100 of the club's own on top of the stakes
+        let mut club = club(100);
+        let mut game = game(2);
+        let mut position = position();
+        let (home, away) = (credential(0, 100, 200), credential(1, 100, 200));
+        club.bet(&mut game, &mut position, 0, 100, 200, 50).unwrap();
+        club.bet(&mut game, &mut position, 1, 100, 200, 50).unwrap();
+        assert_eq!((club.locking, club.liquidity), (200, 300));
+
+        let settlements = vec![Settlement::HalfWin, Settlement::HalfLose];
+        club.post_result(&mut game, settlements, Pubkey::default(), 100).unwrap();
+        // half the lock plus half the stake back, and half the stake back
+        assert_eq!(club.locking, 150 + 50);
+
+        assert_eq!(club.settle(&mut game, &mut position, &home).unwrap(), (50, 100));
+        assert_eq!(club.locking, 50);
+        assert_eq!(club.settle(&mut game, &mut position, &away).unwrap(), (0, 50));
+        assert_eq!((club.locking, club.staking, club.liquidity), (0, 0, 100));
+    }
+
+    #[test]
+    fn settled_exposure_never_falls_below_the_payouts_left() {
+        let mut club = club(1000);
+        let mut game = game(2);
+        let mut position = position();
+        let credentials = [credential(0, 3, 7), credential(0, 5, 8), credential(1, 7, 9)];
+        for credential in &credentials {
+            let (direction, stake, lock) = (credential.direction, credential.stake, credential.lock);
+            club.bet(&mut game, &mut position, direction, stake, lock, 50).unwrap();
+        }
+        let settlements = vec![Settlement::HalfWin, Settlement::HalfLose];
+        club.post_result(&mut game, settlements, Pubkey::default(), 100).unwrap();
+
+        for credential in &credentials {
+            let exposure = game.exposure();
+            let (prize, refund) = club.settle(&mut game, &mut position, credential).unwrap();
+            assert!(prize + refund <= exposure);
+        }
+        assert_eq!((game.exposure(), club.locking), (0, 0));
+    }
+
+    #[test]
+    fn matched_bet_indexes_never_repeat_on_a_game() {
+        let mut game = game(2);
+        game.open_exchange_bet(0, 50).unwrap();
+        game.open_matched_bet(0, 50).unwrap();
+        game.close_exchange_bet();
+        game.close_exchange_bet();
+        game.open_matched_bet(1, 50).unwrap();
+
+        assert_eq!((game.exchange_bets, game.matched_bets), (1, 2));
+        assert_error(game.open_matched_bet(0, 100), SportsError::GameNotOpen);
+    }
 }
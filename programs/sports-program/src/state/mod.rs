@@ -1,5 +1,13 @@
 mod admin;
+mod exchange;
+#[cfg(test)]
+pub(crate) mod fixtures;
 mod game;
+mod operator;
+mod parlay;
 
 pub use admin::*;
+pub use exchange::*;
 pub use game::*;
+pub use operator::*;
+pub use parlay::*;
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+use crate::error::SportsError;
+
+pub const MAX_OPERATORS: usize = 64;
+
+#[derive(Clone, Copy)]
+pub enum Permission {
+    CreateGame = 1 << 0,
+    AcceptBet = 1 << 1,
+    PostResult = 1 << 2,
+    Void = 1 << 3,
+}
+
+impl Permission {
+    pub const ALL: u8 = Self::CreateGame as u8
+        | Self::AcceptBet as u8
+        | Self::PostResult as u8
+        | Self::Void as u8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct Operator {
+    pub key: Pubkey,
+    pub permissions: u8,
+}
+
+impl Operator {
+    pub const SIZE: usize = 32 + 1;
+}
+
+/// Operator list shared by `Admin` and `Club`.
+pub(crate) trait Operators {
+    fn is_operator(&self, operator: &Pubkey) -> bool;
+
+    fn has_permission(&self, operator: &Pubkey, permission: Permission) -> bool;
+
+    /// Number of operators once `operator` is assigned.
+    fn len_with(&self, operator: &Pubkey) -> usize;
+
+    /// Number of operators once `operator` is removed.
+    fn len_without(&self, operator: &Pubkey) -> usize;
+
+    fn assign_operator(&mut self, operator: Pubkey, permissions: u8) -> Result<()>;
+
+    fn remove_operator(&mut self, operator: &Pubkey);
+}
+
+impl Operators for Vec<Operator> {
+    fn is_operator(&self, operator: &Pubkey) -> bool {
+        self.iter().any(|op| &op.key == operator)
+    }
+
+    fn has_permission(&self, operator: &Pubkey, permission: Permission) -> bool {
+        self.iter()
+            .any(|op| &op.key == operator && op.permissions & permission as u8 != 0)
+    }
+
+    fn len_with(&self, operator: &Pubkey) -> usize {
+        if self.is_operator(operator) {
+            self.len()
+        } else {
+            self.len() + 1
+        }
+    }
+
+    fn len_without(&self, operator: &Pubkey) -> usize {
+        if self.is_operator(operator) {
+            self.len() - 1
+        } else {
+            self.len()
+        }
+    }
+
+    fn assign_operator(&mut self, operator: Pubkey, permissions: u8) -> Result<()> {
+        require!(
+            permissions != 0 && permissions & !Permission::ALL == 0,
+            SportsError::InvalidPermissions,
+        );
+        if let Some(op) = self.iter_mut().find(|op| op.key == operator) {
+            op.permissions = permissions;
+            return Ok(());
+        }
+        require_gt!(MAX_OPERATORS, self.len(), SportsError::TooManyOperators);
+        self.push(Operator {
+            key: operator,
+            permissions,
+        });
+
+        Ok(())
+    }
+
+    fn remove_operator(&mut self, operator: &Pubkey) {
+        self.retain(|op| &op.key != operator);
+    }
+}
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{Settlement, ODDS_DIVISOR},
+    error::SportsError,
+};
+
+pub const MIN_PARLAY_LEGS: usize = 2;
+pub const MAX_PARLAY_LEGS: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ParlayLeg {
+    pub game: Pubkey,
+    pub direction: u8,
+    pub odds: u64,
+}
+
+impl ParlayLeg {
+    pub const SIZE: usize = 32 + 1 + 8;
+}
+
+/// A single wager across several games, won only when every leg wins.
+#[account]
+pub struct Parlay {
+    pub club: Pubkey,
+    pub player: Pubkey,
+    // receives the rent back once the parlay is closed
+    pub payer: Pubkey,
+    pub identifier: [u8; 32],
+
+    pub stake: u64,
+    // payout if every leg wins
+    pub lock: u64,
+    pub legs: Vec<ParlayLeg>,
+}
+
+impl Parlay {
+    pub(crate) fn size(legs: usize) -> usize {
+        32 + 32 + 32 + 32 + 8 + 8 + 4 + ParlayLeg::SIZE * legs
+    }
+
+    /// Payout of `stake` compounded over each of `odds`.
+    pub(crate) fn payout(stake: u64, mut odds: impl Iterator<Item = u64>) -> Result<u64> {
+        odds
+            .try_fold(stake as u128, |amount, odds| {
+                amount.checked_mul(odds as u128).map(|amount| amount / ODDS_DIVISOR as u128)
+            })
+            .and_then(|payout| u64::try_from(payout).ok())
+            .ok_or_else(|| SportsError::InvalidLockAmount.into())
+    }
+
+    /// Payout once every leg is resolved, counting voided legs at even odds.
+    /// Returns `None` while some leg is still pending and none has lost.
+    pub(crate) fn resolve(&self, results: &[Option<Settlement>]) -> Result<Option<u64>> {
+        if results.contains(&Some(Settlement::Lose)) {
+            return Ok(Some(0));
+        }
+        if results.contains(&None) {
+            return Ok(None);
+        }
+        let odds = self.legs
+            .iter()
+            .zip(results.iter().flatten())
+            .map(|(leg, settlement)| settlement.leg_odds(leg.odds));
+        Self::payout(self.stake, odds).map(Some)
+    }
+
+    /// Whether resolved `results` beat the stake: no leg lost even half of
+    /// its stake and at least one leg was not voided.
+    pub(crate) fn is_win(results: &[Option<Settlement>]) -> bool {
+        let won = |result: &Option<Settlement>| {
+            matches!(result, Some(Settlement::Win | Settlement::HalfWin))
+        };
+        results.iter().any(won)
+            && results.iter().all(|result| won(result) || *result == Some(Settlement::Push))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::fixtures::parlay;
+
+    #[test]
+    fn pushed_legs_count_at_even_odds() {
+        let parlay = parlay(100, &[20000, 30000, 15000]);
+        assert_eq!(parlay.lock, 900);
+
+        let results = [Some(Settlement::Win), Some(Settlement::Push), Some(Settlement::Win)];
+        assert_eq!(parlay.resolve(&results).unwrap(), Some(300));
+
+        let results = [Some(Settlement::Push); 3];
+        assert_eq!(parlay.resolve(&results).unwrap(), Some(100));
+    }
+
+    #[test]
+    fn half_results_scale_the_leg_odds() {
+        let parlay = parlay(100, &[20000, 20000]);
+
+        let results = [Some(Settlement::HalfWin), Some(Settlement::Win)];
+        assert_eq!(parlay.resolve(&results).unwrap(), Some(300));
+
+        let results = [Some(Settlement::HalfLose), Some(Settlement::Win)];
+        assert_eq!(parlay.resolve(&results).unwrap(), Some(100));
+    }
+
+    #[test]
+    fn lost_leg_settles_before_pending_legs() {
+        let parlay = parlay(100, &[20000, 20000]);
+
+        assert_eq!(parlay.resolve(&[None, Some(Settlement::Lose)]).unwrap(), Some(0));
+        assert_eq!(parlay.resolve(&[None, Some(Settlement::Win)]).unwrap(), None);
+    }
+
+    #[test]
+    fn only_legs_beating_the_stake_make_a_win() {
+        use Settlement::*;
+
+        assert!(Parlay::is_win(&[Some(Win), Some(Push)]));
+        assert!(Parlay::is_win(&[Some(HalfWin), Some(Win)]));
+        assert!(!Parlay::is_win(&[Some(Push), Some(Push)]));
+        assert!(!Parlay::is_win(&[Some(HalfLose), Some(Win)]));
+        assert!(!Parlay::is_win(&[Some(Lose), Some(Win)]));
+        assert!(!Parlay::is_win(&[None, Some(Win)]));
+    }
+}
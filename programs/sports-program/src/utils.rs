@@ -0,0 +1,130 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        ed25519_program,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
+};
+
+use crate::error::SportsError;
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+
+/// Checks that an Ed25519 program instruction placed before the current one
+/// verified `message` signed by `signer`. The Ed25519 program fails the whole
+/// transaction on a bad signature, so finding the entry is enough.
+pub(crate) fn verify_ed25519_signature(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current = load_current_index_checked(instructions)?;
+    for index in 0..current {
+        let ix = load_instruction_at_checked(index as usize, instructions)?;
+        if ix.program_id == ed25519_program::ID && contains_signature(&ix.data, signer, message) {
+            return Ok(());
+        }
+    }
+
+    err!(SportsError::InvalidSignature)
+}
+
+fn contains_signature(data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+    let count = match data.first() {
+        Some(count) => *count as usize,
+        None => return false,
+    };
+    (0..count).any(|i| {
+        let start = SIGNATURE_OFFSETS_START + i * SIGNATURE_OFFSETS_SIZE;
+        let Some(offsets) = data.get(start..start + SIGNATURE_OFFSETS_SIZE) else {
+            return false;
+        };
+        let read = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+        // signature, public key and message must all live in this instruction
+        if read(2) != u16::MAX || read(6) != u16::MAX || read(12) != u16::MAX {
+            return false;
+        }
+        let pubkey_offset = read(4) as usize;
+        let message_offset = read(8) as usize;
+        let message_size = read(10) as usize;
+        data.get(pubkey_offset..pubkey_offset + 32) == Some(signer.as_ref())
+            && data.get(message_offset..message_offset + message_size) == Some(message)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNATURE_SIZE: usize = 64;
+
+    /// Ed25519 program data verifying every `(signer, message)` entry, laid
+    /// out like `new_ed25519_instruction` with all data in this instruction.
+    fn ed25519_data(entries: &[(Pubkey, &[u8])], instruction_index: u16) -> Vec<u8> {
+        let mut data = vec![entries.len() as u8, 0];
+        let mut payload = Vec::new();
+        let payload_start = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE * entries.len();
+        for (signer, message) in entries {
+            let pubkey_offset = payload_start + payload.len();
+            payload.extend_from_slice(signer.as_ref());
+            let signature_offset = payload_start + payload.len();
+            payload.extend_from_slice(&[0; SIGNATURE_SIZE]);
+            let message_offset = payload_start + payload.len();
+            payload.extend_from_slice(message);
+
+            for value in [
+                signature_offset as u16,
+                instruction_index,
+                pubkey_offset as u16,
+                instruction_index,
+                message_offset as u16,
+                message.len() as u16,
+                instruction_index,
+            ] {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        data.extend(payload);
+        data
+    }
+
+    #[test]
+    fn finds_any_signature_in_the_instruction() {
+        let (player, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let data = ed25519_data(&[(other, b"other intent"), (player, b"intent")], u16::MAX);
+
+        assert!(contains_signature(&data, &player, b"intent"));
+        assert!(contains_signature(&data, &other, b"other intent"));
+        assert!(!contains_signature(&data, &player, b"other intent"));
+        assert!(!contains_signature(&data, &Pubkey::new_unique(), b"intent"));
+    }
+
+    #[test]
+    fn rejects_data_held_by_other_instructions() {
+        let player = Pubkey::new_unique();
+        let data = ed25519_data(&[(player, b"intent")], 0);
+
+        assert!(!contains_signature(&data, &player, b"intent"));
+    }
+
+    #[test]
+    fn rejects_malformed_offsets_without_panicking() {
+        let player = Pubkey::new_unique();
+        let data = ed25519_data(&[(player, b"intent")], u16::MAX);
+
+        // more signatures declared than offsets present
+        let mut overcounted = data.clone();
+        overcounted[0] = 200;
+        assert!(contains_signature(&overcounted, &player, b"intent"));
+        assert!(!contains_signature(&overcounted[..SIGNATURE_OFFSETS_START + 1], &player, b"intent"));
+
+        // message running past the end of the data
+        let mut overflowing = data.clone();
+        overflowing[12..14].copy_from_slice(&u16::MAX.to_le_bytes());
+        assert!(!contains_signature(&overflowing, &player, b"intent"));
+
+        assert!(!contains_signature(&[], &player, b"intent"));
+        assert!(!contains_signature(&data[..data.len() - 1], &player, b"intent"));
+    }
+}